@@ -0,0 +1,151 @@
+//! compares parsing with a string allocated for every line by
+//! `io::LineReader` against parsing lines borrowed from a single buffer with
+//! `io::StrLines`. both sides normalize the lines and report diagnostics
+//!
+//! run with `cargo bench --bench lines`. the number of lines generated can be
+//! changed with `AOC_BENCH_LINES`
+
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+use aoc2022::day::{day1, day3};
+use aoc2022::error;
+use aoc2022::io::{LineReader, Normalize};
+use aoc2022::rng::Rng;
+use aoc2022::stats::Summary;
+
+const SAMPLES: usize = 10;
+
+fn day1_input(lines: usize) -> String {
+    let mut rng = Rng::new(1);
+    let mut input = String::with_capacity(lines * 6);
+
+    for _ in 0..lines {
+        if rng.below(6) == 0 {
+            input.push('\n');
+        } else {
+            input.push_str(&(1000 + rng.below(9000)).to_string());
+            input.push('\n');
+        }
+    }
+
+    input
+}
+
+fn day3_input(lines: usize) -> String {
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut rng = Rng::new(3);
+    let mut input = String::with_capacity(lines * 32);
+
+    for _ in 0..lines {
+        let half = 8 + rng.below(12) as usize;
+
+        for _ in 0..(half * 2) {
+            input.push(ITEMS[rng.below(ITEMS.len() as u64) as usize] as char);
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
+
+/// the day 1 parse reading each line into its own string
+fn day1_owned(reader: &mut dyn BufRead) -> error::Result<Vec<(u32, u32)>> {
+    let mut lines = LineReader::new(reader).normalize(Normalize::TRIM);
+    let mut elves = vec![(0u32, 0u32)];
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_empty() {
+            elves.push((0, 0));
+            continue;
+        }
+
+        let Some(calories) = line.parse::<u32>().ok() else {
+            return Err(error::build::bad_input(
+                error::Diagnostic::integer(lines.get_count(), line.as_str(), 0..line.len(), "u32")
+            ));
+        };
+
+        let current = elves.last_mut().unwrap();
+        current.0 = current.0.checked_add(calories).unwrap();
+        current.1 += 1;
+    }
+
+    Ok(elves)
+}
+
+/// the day 3 parse reading each line into its own string
+fn day3_owned(reader: &mut dyn BufRead) -> error::Result<Vec<String>> {
+    let mut lines = LineReader::new(reader).normalize(Normalize::TRIM);
+    let mut rucksacks = Vec::new();
+
+    while let Some(line) = lines.next_line()? {
+        if let Some(index) = line.find(|ch: char| !ch.is_ascii_alphabetic()) {
+            return Err(error::build::bad_input(
+                error::Diagnostic::new(lines.get_count(), line.as_str(), index..index + 1, "expected an item from a-z or A-Z")
+            ));
+        }
+
+        rucksacks.push(line);
+    }
+
+    Ok(rucksacks)
+}
+
+fn measure<F>(mut f: F) -> Summary
+where
+    F: FnMut()
+{
+    // warm up
+    f();
+
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_nanos() as f64
+        })
+        .collect();
+
+    Summary::from_samples(&mut samples).unwrap()
+}
+
+fn report(name: &str, owned: &Summary, borrowed: &Summary) {
+    println!(
+        "{:<6} | {:>12?} | {:>12?} | {:>6.2}x",
+        name,
+        Duration::from_nanos(owned.median as u64),
+        Duration::from_nanos(borrowed.median as u64),
+        owned.median / borrowed.median
+    );
+}
+
+fn main() {
+    let lines: usize = std::env::var("AOC_BENCH_LINES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000_000);
+
+    println!("{} lines, median of {} samples\n", lines, SAMPLES);
+    println!("{:<6} | {:>12} | {:>12} | {:>7}", "day", "owned", "borrowed", "speedup");
+
+    let input = day1_input(lines);
+    let owned = measure(|| {
+        std::hint::black_box(day1_owned(&mut input.as_bytes()).unwrap());
+    });
+    let borrowed = measure(|| {
+        std::hint::black_box(day1::parse_reader(&mut input.as_bytes()).unwrap());
+    });
+    report("day1", &owned, &borrowed);
+
+    let input = day3_input(lines);
+    let owned = measure(|| {
+        std::hint::black_box(day3_owned(&mut input.as_bytes()).unwrap());
+    });
+    let borrowed = measure(|| {
+        std::hint::black_box(day3::parse_reader(&mut input.as_bytes()).unwrap());
+    });
+    report("day3", &owned, &borrowed);
+}
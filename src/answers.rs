@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error;
+use crate::toml;
+use crate::inputs;
+use crate::day::{self, Part, Answer};
+
+/// gets the name used to look up the answers of an input file. this is the
+/// file name without the extension
+pub fn input_name<P>(path: P) -> String
+where
+    P: AsRef<Path>
+{
+    path.as_ref()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// the result of comparing an answer against the stored answer
+pub enum Check {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+/// the known good answers for the puzzle inputs
+///
+/// the answers are stored in a small subset of toml. each input gets a
+/// section named after the day and the input file name without the extension
+/// and each part is a key in the section.
+///
+/// ```toml
+/// [day1.real]
+/// part1 = "70698"
+/// part2 = "206643"
+/// ```
+#[derive(Default)]
+pub struct Answers {
+    expected: HashMap<(u8, String), [Option<String>; 2]>
+}
+
+impl Answers {
+    pub fn load<P>(path: P) -> error::Result<Answers>
+    where
+        P: AsRef<Path>
+    {
+        let contents = match std::fs::read_to_string(path.as_ref()) {
+            Ok(c) => c,
+            Err(err) => {
+                return Err(error::Error::new(error::ErrorKind::IoError)
+                    .with_message(format!("failed to read answers file. {}", path.as_ref().display()))
+                    .with_error(err));
+            }
+        };
+
+        Self::parse(&contents)
+            .map_err(|err| err.in_input(path.as_ref().display().to_string()))
+    }
+
+    pub fn parse(contents: &str) -> error::Result<Answers> {
+        let mut answers = Answers::default();
+
+        for entry in toml::parse(contents)? {
+            let input_key = entry.section.split_once('.')
+                .and_then(|(day_str, input)| {
+                    let day = day_str.strip_prefix("day")?.parse::<u8>().ok()?;
+
+                    Some((day, input.to_owned()))
+                });
+
+            let Some((day, input)) = input_key else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("invalid section in answers file. line {} \"{}\"", entry.line, entry.section)));
+            };
+
+            let Some(part) = entry.key.strip_prefix("part").and_then(Part::try_from_str) else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("unknown key in answers file. line {} \"{}\"", entry.line, entry.key)));
+            };
+
+            answers.insert(day, &input, part, entry.value);
+        }
+
+        Ok(answers)
+    }
+
+    /// the answers of the examples embedded in every registered day
+    pub fn examples() -> Answers {
+        let mut answers = Answers::default();
+
+        for entry in day::REGISTRY {
+            let example = entry.build().example();
+
+            for part in [Part::One, Part::Two] {
+                answers.insert(entry.day, inputs::EXAMPLE, part, example.answer(part).to_owned());
+            }
+        }
+
+        answers
+    }
+
+    /// sets the expected answer for the part of an input
+    pub fn insert(&mut self, day: u8, input: &str, part: Part, value: String) {
+        let parts = self.expected.entry((day, input.to_owned())).or_default();
+
+        match part {
+            Part::One => parts[0] = Some(value),
+            Part::Two => parts[1] = Some(value),
+        }
+    }
+
+    /// retrieves the stored answer for the given part of an input
+    pub fn get(&self, day: u8, input: &str, part: Part) -> Option<&str> {
+        let parts = self.expected.get(&(day, input.to_owned()))?;
+
+        match part {
+            Part::One => parts[0].as_deref(),
+            Part::Two => parts[1].as_deref(),
+        }
+    }
+
+    /// compares an answer to the stored answer
+    pub fn check(&self, day: u8, input: &str, part: Part, answer: &Answer) -> Check {
+        let Some(expected) = self.get(day, input, part) else {
+            return Check::Missing;
+        };
+
+        if answer.to_string() == expected {
+            Check::Pass
+        } else {
+            Check::Fail(expected.to_owned())
+        }
+    }
+}
+
+/// sets the answers of an input in the answers file. the section is replaced
+/// if it exists, otherwise it is added to the end. the rest of the file is
+/// left as is so that comments are kept
+pub fn store<P>(path: P, day: u8, input: &str, answers: &[String; 2]) -> error::Result<()>
+where
+    P: AsRef<Path>
+{
+    let contents = match std::fs::read_to_string(path.as_ref()) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(error::Error::new(error::ErrorKind::IoError)
+                .with_message(format!("failed to read answers file. {}", path.as_ref().display()))
+                .with_error(err));
+        }
+    };
+
+    let header = format!("[day{}.{}]", day, input);
+    let section = format!(
+        "{}\npart1 = \"{}\"\npart2 = \"{}\"\n",
+        header,
+        escape(&answers[0]),
+        escape(&answers[1])
+    );
+
+    let mut updated = String::with_capacity(contents.len() + section.len());
+    let mut lines = contents.lines().peekable();
+    let mut replaced = false;
+
+    while let Some(line) = lines.next() {
+        if line.trim() != header {
+            updated.push_str(line);
+            updated.push('\n');
+            continue;
+        }
+
+        updated.push_str(&section);
+        replaced = true;
+
+        // skip the old keys up to the blank line or section after them
+        while lines.next_if(|next| !next.trim().is_empty() && !next.trim().starts_with('[')).is_some() {}
+    }
+
+    if !replaced {
+        if !updated.is_empty() && !updated.ends_with("\n\n") {
+            updated.push('\n');
+        }
+
+        updated.push_str(&section);
+    }
+
+    std::fs::write(path, updated)?;
+
+    Ok(())
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn store_keeps_the_rest_of_the_file() {
+        let path = std::env::temp_dir().join(format!("aoc2022-answers-{}.toml", std::process::id()));
+        let original = "# comment\n\n[day1.real]\npart1 = \"1\"\npart2 = \"2\"\n\n[day2.real]\npart1 = \"3\"\npart2 = \"4\"\n";
+
+        std::fs::write(&path, original).unwrap();
+
+        store(&path, 1, "real", &[String::from("5"), String::from("6")]).unwrap();
+        store(&path, 1, "stress", &[String::from("7"), String::from("a\"b")]).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            contents,
+            "# comment\n\n[day1.real]\npart1 = \"5\"\npart2 = \"6\"\n\n[day2.real]\npart1 = \"3\"\npart2 = \"4\"\n\n\
+            [day1.stress]\npart1 = \"7\"\npart2 = \"a\\\"b\"\n"
+        );
+
+        let answers = Answers::parse(&contents).unwrap();
+
+        assert_eq!(answers.get(1, "stress", Part::Two), Some("a\"b"));
+        assert_eq!(answers.get(1, "real", Part::One), Some("5"));
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error;
+use crate::toml;
+use crate::stats::Summary;
+use crate::day::Samples;
+
+/// the summarized timings of a single phase of a day
+pub struct Phase {
+    pub name: String,
+    pub summary: Summary,
+}
+
+fn nanos(durations: &[Duration]) -> Vec<f64> {
+    durations.iter()
+        .map(|v| v.as_nanos() as f64)
+        .collect()
+}
+
+/// summarizes each phase of the samples. the phases are named `parse`,
+/// `part1` and `part2`
+pub fn summarize(samples: &Samples) -> Vec<Phase> {
+    let mut phases = Vec::with_capacity(samples.parts.len() + 1);
+
+    if let Some(summary) = Summary::from_samples(&mut nanos(&samples.parse)) {
+        phases.push(Phase {
+            name: String::from("parse"),
+            summary
+        });
+    }
+
+    for (part, times) in &samples.parts {
+        if let Some(summary) = Summary::from_samples(&mut nanos(times)) {
+            phases.push(Phase {
+                name: format!("part{}", part),
+                summary
+            });
+        }
+    }
+
+    phases
+}
+
+/// the median time of each phase from a previous benchmark, keyed by day and
+/// then phase name
+///
+/// the baseline is stored in the same toml subset as the answers file
+///
+/// ```toml
+/// [day1]
+/// parse = 120345
+/// part1 = 2011
+/// ```
+#[derive(Default)]
+pub struct Baseline {
+    medians: BTreeMap<u8, BTreeMap<String, f64>>
+}
+
+impl Baseline {
+    pub fn load<P>(path: P) -> error::Result<Baseline>
+    where
+        P: AsRef<Path>
+    {
+        let contents = match std::fs::read_to_string(path.as_ref()) {
+            Ok(c) => c,
+            Err(err) => {
+                return Err(error::Error::new(error::ErrorKind::IoError)
+                    .with_message(format!("failed to read baseline file. {}", path.as_ref().display()))
+                    .with_error(err));
+            }
+        };
+
+        let mut baseline = Baseline::default();
+
+        let entries = toml::parse(&contents)
+            .map_err(|err| err.in_input(path.as_ref().display().to_string()))?;
+
+        for entry in entries {
+            let day = entry.section.strip_prefix("day")
+                .and_then(|v| v.parse::<u8>().ok());
+
+            let Some(day) = day else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("invalid section in baseline file. line {} \"{}\"", entry.line, entry.section)));
+            };
+
+            let Ok(median) = entry.value.parse::<f64>() else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("baseline value is not a number. line {} \"{}\"", entry.line, entry.value)));
+            };
+
+            baseline.medians.entry(day)
+                .or_default()
+                .insert(entry.key, median);
+        }
+
+        Ok(baseline)
+    }
+
+    /// loads the baseline if the file exists, otherwise an empty baseline
+    pub fn load_or_default<P>(path: P) -> error::Result<Baseline>
+    where
+        P: AsRef<Path>
+    {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Baseline::default())
+        }
+    }
+
+    pub fn save<P>(&self, path: P) -> error::Result<()>
+    where
+        P: AsRef<Path>
+    {
+        let mut contents = String::from("# median nanoseconds of each phase\n");
+
+        for (day, phases) in &self.medians {
+            contents.push_str(&format!("\n[day{}]\n", day));
+
+            for (name, median) in phases {
+                contents.push_str(&format!("{} = {}\n", name, median.round()));
+            }
+        }
+
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, phase: &str) -> Option<f64> {
+        self.medians.get(&day)?.get(phase).copied()
+    }
+
+    /// replaces the stored medians of a day with the given phases
+    pub fn update(&mut self, day: u8, phases: &[Phase]) {
+        let stored = self.medians.entry(day).or_default();
+        stored.clear();
+
+        for phase in phases {
+            stored.insert(phase.name.clone(), phase.summary.median);
+        }
+    }
+}
+
+fn format_nanos(value: f64) -> String {
+    format!("{:?}", Duration::from_nanos(value.max(0.0).round() as u64))
+}
+
+/// prints the summary of each phase and compares the medians to the baseline
+/// if one is given. returns the number of phases that regressed by more than
+/// the threshold percentage
+pub fn report(day: u8, phases: &[Phase], baseline: Option<&Baseline>, threshold: f64) -> usize {
+    let mut regressions: usize = 0;
+    let mut rows: Vec<[String; 8]> = Vec::with_capacity(phases.len() + 1);
+    rows.push([
+        "phase".into(),
+        "min".into(),
+        "median".into(),
+        "mean".into(),
+        "p95".into(),
+        "stddev".into(),
+        "baseline".into(),
+        "change".into(),
+    ]);
+
+    for phase in phases {
+        let mut row = [
+            phase.name.clone(),
+            format_nanos(phase.summary.min),
+            format_nanos(phase.summary.median),
+            format_nanos(phase.summary.mean),
+            format_nanos(phase.summary.p95),
+            format_nanos(phase.summary.stddev),
+            String::from("-"),
+            String::from("-"),
+        ];
+
+        if let Some(median) = baseline.and_then(|b| b.get(day, &phase.name)) {
+            row[6] = format_nanos(median);
+
+            if median > 0.0 {
+                let change = (phase.summary.median - median) / median * 100.0;
+
+                row[7] = format!("{:+.1}%", change);
+
+                if change > threshold {
+                    row[7].push_str(" REGRESSION");
+
+                    regressions += 1;
+                }
+            }
+        }
+
+        rows.push(row);
+    }
+
+    let mut widths = [0usize; 8];
+
+    for row in &rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.len());
+        }
+    }
+
+    for row in &rows {
+        let columns: Vec<String> = row.iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(index, (column, width))| if index == 0 {
+                format!("{:<width$}", column, width = width)
+            } else {
+                format!("{:>width$}", column, width = width)
+            })
+            .collect();
+
+        println!("{}", columns.join(" | ").trim_end());
+    }
+
+    regressions
+}
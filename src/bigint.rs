@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+
+/// the largest power of 10 that fits in a limb. used to parse and print 9
+/// digits at a time
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+/// an unsigned integer with no upper limit
+///
+/// stored as base 2^32 limbs with the least significant first. there are
+/// never any zero limbs at the end so zero has no limbs and each value has
+/// only one representation. only what is needed to total up puzzle values
+/// is supported.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// parses a string of decimal digits. there must be at least one digit
+    /// and nothing else
+    pub fn parse(digits: &str) -> Option<Self> {
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut rtn = BigUint::zero();
+
+        // the first chunk is shorter so the rest are a full 9 digits
+        let first = digits.len() % DECIMAL_DIGITS;
+        let (head, tail) = digits.split_at(first);
+
+        if !head.is_empty() {
+            rtn.add_small(head.parse().ok()?);
+        }
+
+        for chunk in tail.as_bytes().chunks(DECIMAL_DIGITS) {
+            let chunk = std::str::from_utf8(chunk).ok()?.parse().ok()?;
+
+            rtn.mul_small(DECIMAL_BASE);
+            rtn.add_small(chunk);
+        }
+
+        Some(rtn)
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn mul_small(&mut self, value: u32) {
+        let mut carry: u64 = 0;
+
+        for limb in self.limbs.iter_mut() {
+            let product = *limb as u64 * value as u64 + carry;
+
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.trim();
+    }
+
+    fn add_small(&mut self, value: u32) {
+        let mut carry = value as u64;
+
+        for limb in self.limbs.iter_mut() {
+            if carry == 0 {
+                return;
+            }
+
+            let sum = *limb as u64 + carry;
+
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// divides in place and returns the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder: u64 = 0;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 32) | *limb as u64;
+
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+
+        self.trim();
+
+        remainder as u32
+    }
+
+    /// the closest float. very large values lose precision
+    pub fn to_f64(&self) -> f64 {
+        self.limbs.iter()
+            .rev()
+            .fold(0.0, |acc, limb| acc * 4294967296.0 + *limb as f64)
+    }
+
+    /// the value if it fits in a u64
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(*low as u64),
+            [low, high] => Some(((*high as u64) << 32) | *low as u64),
+            _ => None,
+        }
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut limbs = Vec::new();
+
+        while value != 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+
+        BigUint { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint::from(value as u128)
+    }
+}
+
+impl std::ops::AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+
+        let mut carry: u64 = 0;
+
+        for (index, limb) in self.limbs.iter_mut().enumerate() {
+            let add = other.limbs.get(index).copied().unwrap_or(0);
+
+            if add == 0 && carry == 0 && index >= other.limbs.len() {
+                break;
+            }
+
+            let sum = *limb as u64 + add as u64 + carry;
+
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl std::ops::Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut rtn = self.clone();
+        rtn += other;
+        rtn
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // without trailing zero limbs a longer value is always larger
+        self.limbs.len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        let mut value = self.clone();
+        let mut chunks = Vec::new();
+
+        while !value.is_zero() {
+            chunks.push(value.div_rem_small(DECIMAL_BASE));
+        }
+
+        let mut chunks = chunks.iter().rev();
+
+        if let Some(first) = chunks.next() {
+            write!(f, "{}", first)?;
+        }
+
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn parse_and_display() {
+        for digits in ["0", "1", "999999999", "1000000000", "18446744073709551616", "340282366920938463463374607431768211456000"] {
+            assert_eq!(BigUint::parse(digits).unwrap().to_string(), digits);
+        }
+
+        assert_eq!(BigUint::parse("000123").unwrap().to_string(), "123");
+        assert!(BigUint::parse("").is_none());
+        assert!(BigUint::parse("12a").is_none());
+        assert!(BigUint::parse("-1").is_none());
+    }
+
+    #[test]
+    fn matches_u128() {
+        let mut rng = Rng::new(21);
+
+        for _ in 0..1000 {
+            // keep the sum in range of a u128
+            let shift = rng.below(127) as u32;
+            let a = (rng.next_u64() as u128 | ((rng.next_u64() as u128) << 64)) >> (shift + 1);
+            let b = (rng.next_u64() as u128 | ((rng.next_u64() as u128) << 64)) >> (rng.below(127) as u32 + 1);
+
+            let big_a = BigUint::from(a);
+            let big_b = BigUint::from(b);
+
+            assert_eq!((&big_a + &big_b).to_string(), (a + b).to_string());
+            assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+            assert_eq!(BigUint::parse(&a.to_string()).unwrap(), big_a);
+            assert_eq!(big_a.to_u64(), u64::try_from(a).ok());
+        }
+    }
+
+    #[test]
+    fn carries_past_u128() {
+        let mut value = BigUint::from(u128::MAX);
+        value += &BigUint::from(1u64);
+
+        assert_eq!(value.to_string(), "340282366920938463463374607431768211456");
+        assert!(value > BigUint::from(u128::MAX));
+        assert_eq!(value.to_f64(), 2f64.powi(128));
+    }
+}
@@ -1,60 +1,537 @@
-use std::env::Args;
+use std::io::{BufRead, IsTerminal};
 use std::path::PathBuf;
 
 use crate::error;
+use crate::answers;
+use crate::inputs;
 
-/// gets the canonicalized version of the given path
-pub fn get_full_path<P>(path: P) -> error::Result<PathBuf>
+/// the kind of value that an option accepts
+#[derive(Clone, Copy, Debug)]
+pub enum ValueKind {
+    /// the option takes no value
+    Flag,
+    String,
+    Path,
+    Usize,
+    Number,
+    /// one of the listed values
+    Choice(&'static [&'static str]),
+}
+
+impl ValueKind {
+    /// the placeholder shown in the help output
+    fn placeholder(&self) -> String {
+        match self {
+            ValueKind::Flag => String::new(),
+            ValueKind::String => String::from("<string>"),
+            ValueKind::Path => String::from("<path>"),
+            ValueKind::Usize => String::from("<usize>"),
+            ValueKind::Number => String::from("<number>"),
+            ValueKind::Choice(list) => format!("<{}>", list.join("|")),
+        }
+    }
+
+    /// checks that the value can be used for this kind
+    fn validate(&self, value: &str) -> bool {
+        match self {
+            ValueKind::Flag => false,
+            ValueKind::String | ValueKind::Path => true,
+            ValueKind::Usize => value.parse::<usize>().is_ok(),
+            ValueKind::Number => value.parse::<f64>().is_ok(),
+            ValueKind::Choice(list) => list.contains(&value),
+        }
+    }
+}
+
+/// the definition of a command line option
+///
+/// options are declared as constants so that each command and day can list
+/// what it accepts.
+///
+/// ```ignore
+/// const TOP_ELVES: Opt = Opt::new("top-elves", ValueKind::Usize, "the number of top elves to total")
+///     .default("3");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Opt {
+    pub name: &'static str,
+    pub short: Option<char>,
+    pub kind: ValueKind,
+    pub default: Option<&'static str>,
+    pub multiple: bool,
+    /// the option can change the answers so the stored answers do not apply
+    /// when it is given a value other than the default
+    pub changes_answers: bool,
+    pub help: &'static str,
+}
+
+impl Opt {
+    pub const fn new(name: &'static str, kind: ValueKind, help: &'static str) -> Self {
+        Opt {
+            name,
+            short: None,
+            kind,
+            default: None,
+            multiple: false,
+            changes_answers: false,
+            help,
+        }
+    }
+
+    pub const fn flag(name: &'static str, help: &'static str) -> Self {
+        Opt::new(name, ValueKind::Flag, help)
+    }
+
+    pub const fn short(mut self, short: char) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    pub const fn default(mut self, default: &'static str) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// allows the option to be given more than once
+    pub const fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// marks the option as one that can change the answers
+    pub const fn changes_answers(mut self) -> Self {
+        self.changes_answers = true;
+        self
+    }
+}
+
+/// the help option that every command accepts
+pub const HELP: Opt = Opt::flag("help", "prints this help message").short('h');
+
+/// the values from parsing the command line with a list of options
+#[derive(Default, Debug)]
+pub struct Matches {
+    options: Vec<Opt>,
+    values: Vec<(&'static str, String)>,
+    flags: Vec<&'static str>,
+    positionals: Vec<String>,
+}
+
+impl Matches {
+    fn find(&self, name: &str) -> Option<&Opt> {
+        self.options.iter().find(|opt| opt.name == name)
+    }
+
+    /// the number of times a flag was given
+    pub fn count(&self, name: &str) -> usize {
+        self.flags.iter().filter(|flag| **flag == name).count()
+    }
+
+    /// checks if a flag was given
+    pub fn flag(&self, name: &str) -> bool {
+        self.count(name) > 0
+    }
+
+    /// the options that can change the answers and were given a value other
+    /// than their default
+    pub fn changed_answers(&self) -> Vec<&'static str> {
+        self.options.iter()
+            .filter(|opt| opt.changes_answers)
+            .filter(|opt| match opt.kind {
+                ValueKind::Flag => self.flag(opt.name),
+                _ => self.values(opt.name).last().is_some_and(|value| Some(*value) != opt.default),
+            })
+            .map(|opt| opt.name)
+            .collect()
+    }
+
+    /// all the values given for an option in the order they were given
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.values.iter()
+            .filter(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// the value for an option parsed to the requested type. the last value
+    /// given is used, falling back to the default of the option
+    pub fn get<T>(&self, name: &str) -> error::Result<Option<T>>
+    where
+        T: std::str::FromStr
+    {
+        let given = self.values.iter()
+            .rev()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str());
+
+        let Some(value) = given.or_else(|| self.find(name).and_then(|opt| opt.default)) else {
+            return Ok(None);
+        };
+
+        match value.parse() {
+            Ok(v) => Ok(Some(v)),
+            Err(_) => {
+                let expected = self.find(name)
+                    .map(|opt| opt.kind.placeholder())
+                    .unwrap_or_default();
+
+                Err(error::build::invalid_value(name, expected, value))
+            }
+        }
+    }
+
+    /// the same as [`Matches::get`] but the option is required to have a
+    /// value
+    pub fn require<T>(&self, name: &str) -> error::Result<T>
+    where
+        T: std::str::FromStr
+    {
+        let Some(value) = self.get(name)? else {
+            return Err(error::build::missing_value(name));
+        };
+
+        Ok(value)
+    }
+
+    /// the arguments that were not options
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+}
+
+/// parses arguments with the given list of options
+///
+/// long options accept `--name value` and `--name=value`. short options can
+/// be clustered together, `-vv` or `-cf file`, with the first option that
+/// takes a value using the rest of the cluster or the next argument. `--`
+/// ends the options. if `stop_at_positional` is set then parsing stops after
+/// the first positional argument and the remaining arguments are left in the
+/// iterator.
+pub fn parse<I>(options: &[&[Opt]], args: &mut I, stop_at_positional: bool) -> error::Result<Matches>
 where
-    P: Into<PathBuf>
+    I: Iterator<Item = String>
 {
-    let path = path.into();
-
-    let to_check = if !path.is_absolute() {
-        let mut cwd = std::env::current_dir()?;
-        cwd.push(path);
-        cwd
-    } else {
-        path
+    let mut matches = Matches {
+        options: options.iter()
+            .flat_map(|list| list.iter().copied())
+            .collect(),
+        ..Default::default()
     };
 
-    let rtn = std::fs::canonicalize(to_check)?;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            matches.positionals.extend(args.by_ref());
+            break;
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (long, None)
+            };
+
+            let Some(opt) = matches.find(name).copied() else {
+                return Err(error::build::invalid_argument(arg));
+            };
+
+            take_value(&mut matches, &opt, inline, args)?;
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            let cluster = &arg[1..];
+
+            for (index, ch) in cluster.char_indices() {
+                let Some(opt) = matches.options.iter().find(|opt| opt.short == Some(ch)).copied() else {
+                    return Err(error::build::invalid_argument(format!("-{}", ch)));
+                };
+
+                if let ValueKind::Flag = opt.kind {
+                    take_value(&mut matches, &opt, None, args)?;
+                    continue;
+                }
 
-    Ok(rtn)
+                let rest = &cluster[(index + ch.len_utf8())..];
+                let inline = if rest.is_empty() {
+                    None
+                } else {
+                    Some(rest.to_owned())
+                };
+
+                take_value(&mut matches, &opt, inline, args)?;
+                break;
+            }
+        } else {
+            matches.positionals.push(arg);
+
+            if stop_at_positional {
+                break;
+            }
+        }
+    }
+
+    Ok(matches)
 }
 
-/// attempts to retrieve the next argument
-/// 
-/// if the argument is not present then it will return an error indicating the
-/// argument is missing and provide the name of the argument
-pub fn get_arg_value<N>(args: &mut Args, name: N) -> error::Result<String>
+/// stores the value of an option, taking the next argument if the option
+/// needs a value that was not given inline
+fn take_value<I>(matches: &mut Matches, opt: &Opt, inline: Option<String>, args: &mut I) -> error::Result<()>
 where
-    N: AsRef<str>
+    I: Iterator<Item = String>
 {
-    let Some(v) = args.next() else {
-        let mut msg = String::from("missing ");
-        msg.push_str(name.as_ref());
-        msg.push_str(" argument value");
+    if let ValueKind::Flag = opt.kind {
+        if inline.is_some() {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message(format!("--{} does not take a value", opt.name)));
+        }
 
-        return Err(error::Error::new(error::ErrorKind::MissingArgument)
-            .with_message(msg))
+        matches.flags.push(opt.name);
+
+        return Ok(());
+    }
+
+    let Some(value) = inline.or_else(|| args.next()) else {
+        return Err(error::build::missing_value(opt.name));
     };
 
-    Ok(v)
+    if !opt.kind.validate(&value) {
+        return Err(error::build::invalid_value(opt.name, opt.kind.placeholder(), &value));
+    }
+
+    if !opt.multiple && matches.values.iter().any(|(key, _)| *key == opt.name) {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!("--{} can only be given once", opt.name)));
+    }
+
+    matches.values.push((opt.name, value));
+
+    Ok(())
 }
 
-pub fn get_file_reader<P>(file_path: Option<P>) -> error::Result<std::io::BufReader<std::fs::File>>
+/// formats a section of the help output for a list of options
+pub fn help_section(title: &str, options: &[Opt]) -> String {
+    let mut lines: Vec<(String, String)> = Vec::with_capacity(options.len());
+
+    for opt in options {
+        let mut left = match opt.short {
+            Some(short) => format!("  -{}, --{}", short, opt.name),
+            None => format!("      --{}", opt.name),
+        };
+
+        if !matches!(opt.kind, ValueKind::Flag) {
+            left.push(' ');
+            left.push_str(&opt.kind.placeholder());
+        }
+
+        let mut right = String::from(opt.help);
+
+        if let Some(default) = opt.default {
+            right.push_str(&format!(" [default: {}]", default));
+        }
+
+        if opt.multiple {
+            right.push_str(" (can be repeated)");
+        }
+
+        lines.push((left, right));
+    }
+
+    let width = lines.iter()
+        .map(|(left, _)| left.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut rtn = format!("{}:\n", title);
+
+    for (left, right) in lines {
+        rtn.push_str(&format!("{:<width$}  {}\n", left, right, width = width));
+    }
+
+    rtn
+}
+
+pub fn get_file_reader<P>(file_path: P) -> error::Result<std::io::BufReader<std::fs::File>>
 where
     P: Into<PathBuf>
 {
-    let Some(path_ref) = file_path else {
-        return Err(error::build::no_file_provided())
-    };
+    let file_path = file_path.into();
 
-    let full_path = get_full_path(path_ref)?;
-    let file = std::fs::OpenOptions::new()
+    let opened = std::fs::OpenOptions::new()
         .read(true)
-        .open(full_path)?;
+        .open(&file_path);
+
+    match opened {
+        Ok(file) => Ok(std::io::BufReader::new(file)),
+        Err(err) => Err(error::Error::new(error::ErrorKind::IoError)
+            .with_message(format!("failed to open input file. {}", file_path.display()))
+            .with_error(err))
+    }
+}
+
+/// where the puzzle input for a day is read from
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+    /// the example embedded in a day
+    Example(&'static str),
+}
+
+impl InputSource {
+    /// creates the source from the value of a file argument. `-` is stdin
+    pub fn from_arg(arg: String) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(arg.into())
+        }
+    }
+
+    /// stdin if something is being piped in. a terminal or a device such
+    /// as `/dev/null` is not treated as piped input
+    pub fn piped() -> Option<Self> {
+        if std::io::stdin().is_terminal() {
+            return None;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+
+            let file_type = std::fs::metadata("/dev/stdin").ok()?.file_type();
+
+            if !file_type.is_fifo() && !file_type.is_file() {
+                return None;
+            }
+        }
+
+        Some(InputSource::Stdin)
+    }
+
+    pub fn open(&self) -> error::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            InputSource::File(path) => Ok(Box::new(get_file_reader(path)?)),
+            InputSource::Example(data) => Ok(Box::new(data.as_bytes())),
+        }
+    }
+
+    /// the name used to look up the stored answers for the input
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Stdin => String::from("stdin"),
+            InputSource::File(path) => answers::input_name(path),
+            InputSource::Example(_) => String::from(inputs::EXAMPLE),
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => f.write_str("stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Example(_) => f.write_str("embedded example"),
+        }
+    }
+}
 
-    Ok(std::io::BufReader::new(file))
-}
\ No newline at end of file
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OPTIONS: &[Opt] = &[
+        Opt::flag("verbose", "more output").short('v'),
+        Opt::flag("check", "check answers").short('c'),
+        Opt::new("file", ValueKind::Path, "input file")
+            .short('f')
+            .multiple(),
+        Opt::new("top", ValueKind::Usize, "top count")
+            .default("3")
+            .changes_answers(),
+        Opt::new("width", ValueKind::Choice(&["u32", "u64"]), "width")
+            .changes_answers(),
+    ];
+
+    fn parse_args(args: &[&str]) -> error::Result<Matches> {
+        let mut args = args.iter().map(|arg| arg.to_string());
+
+        parse(&[OPTIONS], &mut args, false)
+    }
+
+    fn message(err: error::Error) -> String {
+        err.message.unwrap_or_default()
+    }
+
+    #[test]
+    fn long_values() {
+        let matches = parse_args(&["--top=5", "--width", "u64"]).unwrap();
+
+        assert_eq!(matches.require::<usize>("top").unwrap(), 5);
+        assert_eq!(matches.get::<String>("width").unwrap().as_deref(), Some("u64"));
+
+        // an empty inline value is still a value
+        assert!(parse_args(&["--file="]).unwrap().values("file") == vec![""]);
+        assert_eq!(parse_args(&[]).unwrap().require::<usize>("top").unwrap(), 3);
+    }
+
+    #[test]
+    fn short_clusters() {
+        let matches = parse_args(&["-vv", "-cf", "input.txt", "-finline.txt"]).unwrap();
+
+        assert_eq!(matches.count("verbose"), 2);
+        assert!(matches.flag("check"));
+        assert_eq!(matches.values("file"), vec!["input.txt", "inline.txt"]);
+
+        // the rest of the cluster is the value of the first option that
+        // takes one
+        assert_eq!(parse_args(&["-fvc"]).unwrap().values("file"), vec!["vc"]);
+    }
+
+    #[test]
+    fn terminator() {
+        let matches = parse_args(&["-v", "--", "--top", "-c", "day"]).unwrap();
+
+        assert_eq!(matches.count("verbose"), 1);
+        assert!(!matches.flag("check"));
+        assert_eq!(matches.positionals(), &["--top", "-c", "day"]);
+    }
+
+    #[test]
+    fn stop_at_positional() {
+        let mut args = ["-v", "1", "--top", "5"].iter().map(|arg| arg.to_string());
+        let matches = parse(&[OPTIONS], &mut args, true).unwrap();
+
+        assert_eq!(matches.positionals(), &["1"]);
+        assert_eq!(args.collect::<Vec<String>>(), vec!["--top", "5"]);
+    }
+
+    #[test]
+    fn changed_answers() {
+        assert!(parse_args(&["-v", "-f", "a"]).unwrap().changed_answers().is_empty());
+        assert!(parse_args(&["--top", "3"]).unwrap().changed_answers().is_empty());
+        assert_eq!(parse_args(&["--top", "5"]).unwrap().changed_answers(), vec!["top"]);
+        assert_eq!(parse_args(&["--width", "u32", "--top", "4"]).unwrap().changed_answers(), vec!["top", "width"]);
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse_args(&["--top", "1", "--top=2"]).unwrap_err();
+
+        assert!(matches!(err.kind, error::ErrorKind::InvalidArgument));
+        assert_eq!(message(err), "--top can only be given once");
+
+        let err = parse_args(&["--top"]).unwrap_err();
+
+        assert!(matches!(err.kind, error::ErrorKind::MissingArgument));
+        assert_eq!(message(err), "missing --top argument value");
+
+        let err = parse_args(&["--width", "u8"]).unwrap_err();
+
+        assert!(matches!(err.kind, error::ErrorKind::InvalidArgument));
+        assert_eq!(message(err), "invalid value for --width. expected <u32|u64>. value: u8");
+
+        assert_eq!(message(parse_args(&["--top", "-1"]).unwrap_err()), "invalid value for --top. expected <usize>. value: -1");
+        assert_eq!(message(parse_args(&["--nope"]).unwrap_err()), "given invalid argument. \"--nope\"");
+        assert_eq!(message(parse_args(&["-vx"]).unwrap_err()), "given invalid argument. \"-x\"");
+        assert_eq!(message(parse_args(&["--verbose=yes"]).unwrap_err()), "--verbose does not take a value");
+        assert!(matches!(parse_args(&[]).unwrap().require::<String>("width").unwrap_err().kind, error::ErrorKind::MissingArgument));
+    }
+}
@@ -0,0 +1,946 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error;
+use crate::cli;
+use crate::day;
+use crate::answers;
+use crate::bench;
+use crate::trace;
+use crate::inputs;
+use crate::fuzz;
+use crate::rng;
+use crate::gen;
+use crate::output::{Output, Format, Record};
+
+/// prints the registered days and the parts they implement
+pub fn list() {
+    for entry in day::REGISTRY {
+        let runner = entry.build();
+        let parts: Vec<String> = runner.parts()
+            .iter()
+            .map(|part| part.to_string())
+            .collect();
+
+        println!("day {}: {} (parts: {})", entry.day, entry.title, parts.join(", "));
+    }
+}
+
+/// the options for running a single day
+const RUN_OPTIONS: &[cli::Opt] = &[
+    cli::Opt::new("file", cli::ValueKind::Path, "the puzzle input to run. `-` reads from stdin")
+        .short('f')
+        .multiple(),
+    cli::Opt::new("input", cli::ValueKind::String, "run a named input from the input directory. example, real or stress")
+        .short('i')
+        .multiple(),
+    cli::Opt::flag("example", "run the example embedded in the day and check its answers"),
+    cli::Opt::new("part", cli::ValueKind::Choice(&["1", "2"]), "only run the given part")
+        .short('p')
+        .multiple(),
+    cli::Opt::flag("check", "compare the answers to the expected answers"),
+    cli::Opt::new("answers", cli::ValueKind::Path, "the expected answers file")
+        .default("inputs/answers.toml"),
+    cli::Opt::new("report-file", cli::ValueKind::Path, "write the report of a day, like day 1 --stats, to a file instead of printing it"),
+    cli::Opt::new("bench", cli::ValueKind::Usize, "benchmark the day with the given number of iterations"),
+    cli::Opt::new("warmup", cli::ValueKind::Usize, "the number of unrecorded runs before benchmarking")
+        .default("3"),
+    cli::Opt::new("baseline", cli::ValueKind::Path, "compare the benchmark to a saved baseline"),
+    cli::Opt::new("save-baseline", cli::ValueKind::Path, "save the benchmark medians to a baseline file"),
+    cli::Opt::new("threshold", cli::ValueKind::Number, "the percent slower than the baseline that is a regression")
+        .default("10"),
+    cli::HELP,
+];
+
+/// the options for running every day
+const ALL_OPTIONS: &[cli::Opt] = &[
+    cli::Opt::flag("example", "run the example embedded in each day and check its answers"),
+    cli::Opt::flag("check", "compare the answers to the expected answers"),
+    cli::Opt::new("answers", cli::ValueKind::Path, "the expected answers file")
+        .default("inputs/answers.toml"),
+    cli::HELP,
+];
+
+/// the inputs to run when no file is given. named inputs are looked up in
+/// the input directory. without names the piped input is used if there is
+/// one, otherwise the real input
+fn default_inputs(day: u8, matches: &cli::Matches, piped: Option<cli::InputSource>) -> error::Result<Vec<cli::InputSource>> {
+    let names = matches.values("input");
+
+    if names.is_empty() {
+        if let Some(stdin) = piped {
+            return Ok(vec![stdin]);
+        }
+    }
+
+    let store = inputs::store(matches)?;
+    let fetcher = inputs::fetcher(matches)?;
+    let names = if names.is_empty() { vec![inputs::REAL] } else { names };
+
+    names.into_iter()
+        .map(|name| Ok(cli::InputSource::File(store.resolve(day, name, fetcher.as_deref())?)))
+        .collect()
+}
+
+/// runs the given day with the remaining arguments
+pub fn run<I>(day: String, args: &mut I, out: &mut Output) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let entry = match day.parse::<u8>() {
+        Ok(num) => day::get(num),
+        Err(_) => None
+    };
+
+    let Some(entry) = entry else {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!("unknown day specified. given: {}", day)));
+    };
+
+    let mut runner = entry.build();
+    let matches = cli::parse(&[RUN_OPTIONS, inputs::OPTIONS, trace::OPTIONS, runner.options()], args, false)?;
+
+    if matches.flag("help") {
+        print!(
+            "usage: aoc2022 {} [options]\n\nday {}: {}\n\n{}",
+            entry.day,
+            entry.day,
+            entry.title,
+            cli::help_section("options", RUN_OPTIONS)
+        );
+
+        print!("\n{}", cli::help_section("input options", inputs::OPTIONS));
+        print!("\n{}", cli::help_section("trace options", trace::OPTIONS));
+
+        if !runner.options().is_empty() {
+            print!("\n{}", cli::help_section("day options", runner.options()));
+        }
+
+        return Ok(());
+    }
+
+    if let Some(arg) = matches.positionals().first() {
+        return Err(error::build::invalid_argument(arg.clone()));
+    }
+
+    runner.configure(&matches)?;
+    trace::configure(&matches)?;
+
+    let mut inputs: Vec<cli::InputSource> = matches.values("file")
+        .into_iter()
+        .map(|v| cli::InputSource::from_arg(v.to_owned()))
+        .collect();
+    let mut parts: Vec<day::Part> = matches.values("part")
+        .into_iter()
+        .filter_map(day::Part::try_from_str)
+        .collect();
+    let example = matches.flag("example");
+    let check = matches.flag("check") || example;
+    let answers_path: PathBuf = matches.require("answers")?;
+    let bench_iterations: Option<usize> = matches.get("bench")?;
+    let warmup: usize = matches.require("warmup")?;
+    let threshold: f64 = matches.require("threshold")?;
+    let baseline_path: Option<PathBuf> = matches.get("baseline")?;
+    let save_baseline_path: Option<PathBuf> = matches.get("save-baseline")?;
+    let report_path: Option<PathBuf> = matches.get("report-file")?;
+
+    if bench_iterations == Some(0) {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message("bench iterations is 0"));
+    }
+
+    if bench_iterations.is_some() && out.format() != Format::Text {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message("benchmarks can only be printed with the text format"));
+    }
+
+    if parts.is_empty() {
+        parts.extend_from_slice(runner.parts());
+    }
+
+    if example {
+        if !inputs.is_empty() || !matches.values("input").is_empty() {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("the example cannot be used with other inputs"));
+        }
+
+        inputs.push(cli::InputSource::Example(runner.example().input));
+    } else if inputs.is_empty() {
+        inputs = default_inputs(entry.day, &matches, cli::InputSource::piped())?;
+    }
+
+    // label the output of each input when there is more than one
+    let labeled = inputs.len() > 1;
+
+    if labeled && report_path.is_some() {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message("a report file can only be used with a single input"));
+    }
+
+    if runner.report_format() != Format::Text && report_path.is_none() {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message("a report that is not text has to be written with --report-file so that it is not mixed with the answers"));
+    }
+
+    if let Some(iterations) = bench_iterations {
+        if labeled && (baseline_path.is_some() || save_baseline_path.is_some()) {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("baselines can only be used with a single input"));
+        }
+
+        for input in &inputs {
+            let mut reader = input.open()?;
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data)?;
+
+            let samples = runner.bench(&data, &parts, warmup, iterations)
+                .map_err(|err| err.in_input(input.to_string()))?;
+            let phases = bench::summarize(&samples);
+
+            let baseline = match &baseline_path {
+                Some(path) => Some(bench::Baseline::load(path)?),
+                None => None
+            };
+
+            println!("day {} {}: {} iterations after {} warm up", entry.day, input, iterations, warmup);
+
+            let regressions = bench::report(entry.day, &phases, baseline.as_ref(), threshold);
+
+            if let Some(path) = &save_baseline_path {
+                let mut saving = bench::Baseline::load_or_default(path)?;
+                saving.update(entry.day, &phases);
+                saving.save(path)?;
+            }
+
+            if regressions > 0 {
+                return Err(error::build::regression_found(regressions, threshold));
+            }
+        }
+
+        return Ok(());
+    }
+
+    // the stored answers are for the default options
+    let changed = matches.changed_answers();
+
+    if check && !changed.is_empty() {
+        let names: Vec<String> = changed.iter().map(|name| format!("--{}", name)).collect();
+
+        eprintln!("warning: the answers are not checked since {} can change them", names.join(", "));
+    }
+
+    let expected = if !changed.is_empty() {
+        None
+    } else if example {
+        Some(answers::Answers::examples())
+    } else if check {
+        Some(answers::Answers::load(&answers_path)?)
+    } else {
+        None
+    };
+
+    let mut mismatches: usize = 0;
+    let mut failures: usize = 0;
+
+    for input in &inputs {
+        if labeled && out.format() == Format::Text {
+            println!("==> {} <==", input);
+        }
+
+        let result = run_input(entry.day, runner.as_ref(), input, &parts, expected.as_ref(), report_path.as_deref(), out);
+
+        match result {
+            Ok(count) => {
+                mismatches += count;
+            },
+            Err(err) => {
+                if !labeled {
+                    return Err(err);
+                }
+
+                if out.format() == Format::Text {
+                    eprintln!("{}", err.summary());
+
+                    if let Some(diagnostic) = &err.diagnostic {
+                        eprintln!("{}", diagnostic.render());
+                    }
+                } else {
+                    out.write_record(&Record::from_error(Some(entry.day), Some(input.to_string()), &err))?;
+                }
+
+                failures += 1;
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        return Err(error::build::check_failed(mismatches));
+    }
+
+    if failures > 0 {
+        return Err(error::Error::new(error::ErrorKind::BadInput)
+            .with_message(format!("{} of {} inputs failed", failures, inputs.len())));
+    }
+
+    Ok(())
+}
+
+/// runs a single input for a day and writes the results. returns the number
+/// of answers that did not match the expected answers
+fn run_input(
+    day: u8,
+    runner: &dyn day::Runner,
+    input: &cli::InputSource,
+    parts: &[day::Part],
+    expected: Option<&answers::Answers>,
+    report_path: Option<&Path>,
+    out: &mut Output
+) -> error::Result<usize> {
+    let input_name = input.name();
+
+    let mut reader = input.open()?;
+    let outcome = runner.run(&mut reader, parts)
+        .map_err(|err| err.in_input(input.to_string()))?;
+
+    // the report has its own layout so it is kept out of json and csv
+    // records
+    if let Some(report) = &outcome.report {
+        if let Some(path) = report_path {
+            std::fs::write(path, report)?;
+        } else if out.format() != Format::Text {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("a report can only be printed with the text format. use --report-file to write it to a file"));
+        }
+    }
+
+    let mut mismatches: usize = 0;
+
+    for part in &outcome.parts {
+        let (status, note) = match expected {
+            Some(expected) => match expected.check(day, &input_name, part.part, &part.answer) {
+                answers::Check::Pass => ("pass", String::from(" (pass)")),
+                answers::Check::Fail(value) => {
+                    mismatches += 1;
+
+                    ("fail", format!(" (fail, expected {})", value))
+                },
+                answers::Check::Missing => ("unchecked", String::from(" (no stored answer)")),
+            },
+            None => ("ok", String::new())
+        };
+
+        if out.format() == Format::Text {
+            println!("part {}: {}{}", part.part, part.answer, note);
+        } else {
+            out.write_record(&Record {
+                day: Some(day),
+                part: Some(part.part),
+                answer: Some(part.answer.to_string()),
+                status,
+                parse_time: Some(outcome.parse_time),
+                solve_time: Some(part.time),
+                input: Some(input.to_string()),
+                error: None,
+            })?;
+        }
+    }
+
+    if out.format() == Format::Text && report_path.is_none() {
+        if let Some(report) = &outcome.report {
+            print!("{}", report);
+        }
+
+        println!("parse time: {:#?}", outcome.parse_time);
+
+        for part in &outcome.parts {
+            println!("part {} time: {:#?}", part.part, part.time);
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// a row in the summary table printed by [`all`]
+struct SummaryRow {
+    day: String,
+    part1: String,
+    part2: String,
+    parse: String,
+    solve: String,
+    status: String,
+}
+
+/// runs every registered day against its puzzle input in the inputs directory
+pub fn all<I>(args: &mut I, out: &mut Output) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let matches = cli::parse(&[ALL_OPTIONS, inputs::OPTIONS, trace::OPTIONS], args, false)?;
+
+    if matches.flag("help") {
+        print!(
+            "usage: aoc2022 all [options]\n\nruns every day with its puzzle input\n\n{}",
+            cli::help_section("options", ALL_OPTIONS)
+        );
+
+        print!("\n{}", cli::help_section("input options", inputs::OPTIONS));
+        print!("\n{}", cli::help_section("trace options", trace::OPTIONS));
+
+        return Ok(());
+    }
+
+    if let Some(arg) = matches.positionals().first() {
+        return Err(error::build::invalid_argument(arg.clone()));
+    }
+
+    trace::configure(&matches)?;
+
+    let store = inputs::store(&matches)?;
+    let fetcher = inputs::fetcher(&matches)?;
+    let example = matches.flag("example");
+    let check = matches.flag("check") || example;
+    let answers_path: PathBuf = matches.require("answers")?;
+    let input_name = if example {
+        inputs::EXAMPLE
+    } else {
+        inputs::REAL
+    };
+
+    let expected = if example {
+        Some(answers::Answers::examples())
+    } else if check {
+        Some(answers::Answers::load(&answers_path)?)
+    } else {
+        None
+    };
+
+    let mut mismatches: usize = 0;
+    let mut failures: usize = 0;
+    let mut rows: Vec<SummaryRow> = Vec::with_capacity(day::REGISTRY.len() + 1);
+    rows.push(SummaryRow {
+        day: "day".into(),
+        part1: "part 1".into(),
+        part2: "part 2".into(),
+        parse: "parse".into(),
+        solve: "solve".into(),
+        status: "status".into(),
+    });
+
+    for entry in day::REGISTRY {
+        let mut row = SummaryRow {
+            day: entry.day.to_string(),
+            part1: String::from("-"),
+            part2: String::from("-"),
+            parse: String::from("-"),
+            solve: String::from("-"),
+            status: String::new(),
+        };
+
+        let runner = entry.build();
+        let source = if example {
+            Ok(cli::InputSource::Example(runner.example().input))
+        } else {
+            store.resolve(entry.day, inputs::REAL, fetcher.as_deref())
+                .map(cli::InputSource::File)
+        };
+        let input = source.as_ref()
+            .ok()
+            .map(|v| v.to_string());
+        let result = source.and_then(|source| source.open())
+            .and_then(|mut reader| runner.run(&mut reader, runner.parts()));
+
+        match result {
+            Ok(outcome) => {
+                let mut solve_time = std::time::Duration::ZERO;
+                let mut status: Vec<String> = Vec::new();
+                let mut failed = false;
+                let mut passed = false;
+
+                for part in outcome.parts {
+                    let mut record_status = "ok";
+
+                    if let Some(expected) = &expected {
+                        match expected.check(entry.day, input_name, part.part, &part.answer) {
+                            answers::Check::Pass => {
+                                record_status = "pass";
+                                passed = true;
+                            },
+                            answers::Check::Fail(value) => {
+                                status.push(format!("part {} expected {}", part.part, value));
+
+                                record_status = "fail";
+                                failed = true;
+                            },
+                            answers::Check::Missing => {
+                                status.push(format!("part {} has no stored answer", part.part));
+
+                                record_status = "unchecked";
+                            }
+                        }
+                    }
+
+                    out.write_record(&Record {
+                        day: Some(entry.day),
+                        part: Some(part.part),
+                        answer: Some(part.answer.to_string()),
+                        status: record_status,
+                        parse_time: Some(outcome.parse_time),
+                        solve_time: Some(part.time),
+                        input: input.clone(),
+                        error: None,
+                    })?;
+
+                    match part.part {
+                        day::Part::One => row.part1 = part.answer.to_string(),
+                        day::Part::Two => row.part2 = part.answer.to_string(),
+                    }
+
+                    solve_time += part.time;
+                }
+
+                row.parse = format!("{:?}", outcome.parse_time);
+                row.solve = format!("{:?}", solve_time);
+
+                if failed {
+                    row.status = format!("fail {}", status.join(", "));
+
+                    mismatches += 1;
+                } else if status.is_empty() {
+                    // only runs that were compared to an answer pass
+                    row.status = String::from(if passed { "pass" } else { "ok" });
+                } else {
+                    row.status = format!("{} {}", if passed { "pass" } else { "unchecked" }, status.join(", "));
+                }
+            },
+            Err(err) => {
+                row.status = format!("fail {}", err.summary());
+
+                out.write_record(&Record::from_error(Some(entry.day), input, &err))?;
+
+                failures += 1;
+            }
+        }
+
+        rows.push(row);
+    }
+
+    if out.format() == Format::Text {
+        print_table(&rows);
+    }
+
+    if mismatches > 0 {
+        return Err(error::build::check_failed(mismatches));
+    }
+
+    // a day that could not run fails the command even without --check
+    if failures > 0 {
+        return Err(error::Error::new(error::ErrorKind::BadInput)
+            .with_message(format!("{} of {} days failed", failures, day::REGISTRY.len())));
+    }
+
+    Ok(())
+}
+
+/// prints the rows with each column padded to the widest value
+fn print_table(rows: &[SummaryRow]) {
+    let mut widths = [0usize; 5];
+
+    for row in rows {
+        let columns = [&row.day, &row.part1, &row.part2, &row.parse, &row.solve];
+
+        for (width, column) in widths.iter_mut().zip(columns) {
+            *width = (*width).max(column.len());
+        }
+    }
+
+    for row in rows {
+        println!(
+            "{:<w0$} | {:<w1$} | {:<w2$} | {:>w3$} | {:>w4$} | {}",
+            row.day, row.part1, row.part2, row.parse, row.solve, row.status,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+    }
+}
+
+/// the options for managing the inputs directory
+const INPUTS_OPTIONS: &[cli::Opt] = &[
+    cli::HELP,
+];
+
+/// the days given as positional arguments or every registered day if none
+/// are given
+fn days_from_args(args: &[String]) -> error::Result<Vec<u8>> {
+    if args.is_empty() {
+        return Ok(day::REGISTRY.iter().map(|entry| entry.day).collect());
+    }
+
+    let mut days = Vec::with_capacity(args.len());
+
+    for arg in args {
+        match arg.parse::<u8>().ok().and_then(day::get) {
+            Some(entry) => days.push(entry.day),
+            None => {
+                return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                    .with_message(format!("unknown day specified. given: {}", arg)));
+            }
+        }
+    }
+
+    Ok(days)
+}
+
+/// manages the files in the inputs directory
+pub fn inputs<I>(args: &mut I) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let matches = cli::parse(&[INPUTS_OPTIONS, inputs::OPTIONS], args, false)?;
+
+    if matches.flag("help") {
+        print!(
+            "usage: aoc2022 inputs [options] <action> [day...]\n\n\
+            manages the named inputs of each day. defaults to every day\n\n\
+            actions:\n  \
+                status  compares the inputs to the manifest\n  \
+                record  saves the hashes of the inputs to the manifest\n  \
+                fetch   downloads the real input\n\n{}\n{}",
+            cli::help_section("options", INPUTS_OPTIONS),
+            cli::help_section("input options", inputs::OPTIONS)
+        );
+
+        return Ok(());
+    }
+
+    let Some((action, rest)) = matches.positionals().split_first() else {
+        return Err(error::Error::new(error::ErrorKind::MissingArgument)
+            .with_message("no inputs action specified"));
+    };
+
+    let store = inputs::store(&matches)?;
+    let days = days_from_args(rest)?;
+
+    match action.as_str() {
+        "status" => {
+            let manifest = store.manifest()?;
+            let mut problems: usize = 0;
+
+            for day in days {
+                let mut names = store.names(day)?;
+
+                if !names.iter().any(|name| name == inputs::REAL) {
+                    names.insert(0, inputs::REAL.to_owned());
+                }
+
+                for name in names {
+                    let status = store.status(&manifest, day, &name)?;
+
+                    if matches!(status, inputs::Status::Modified | inputs::Status::Invalid(_)) {
+                        problems += 1;
+                    }
+
+                    println!("day{}.{}: {}", day, name, status);
+                }
+            }
+
+            if problems > 0 {
+                return Err(error::Error::new(error::ErrorKind::CheckFailed)
+                    .with_message(format!("{} input(s) are modified or invalid", problems)));
+            }
+        },
+        "record" => {
+            let mut manifest = store.manifest()?;
+
+            for day in days {
+                for name in store.names(day)? {
+                    store.record(&mut manifest, day, &name)?;
+
+                    println!("recorded day{}.{}", day, name);
+                }
+            }
+
+            manifest.save(store.manifest_path())?;
+        },
+        "fetch" => {
+            let Some(fetcher) = inputs::fetcher(&matches)? else {
+                return Err(error::Error::new(error::ErrorKind::MissingArgument)
+                    .with_message("a session token is needed to fetch inputs. use --session or AOC_SESSION"));
+            };
+
+            for day in days {
+                let path = store.fetch(fetcher.as_ref(), day)?;
+
+                println!("fetched day {} to {}", day, path.display());
+            }
+        },
+        _ => {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message(format!("unknown inputs action. given: {}", action)));
+        }
+    }
+
+    Ok(())
+}
+
+/// the options for fuzzing the days
+const FUZZ_OPTIONS: &[cli::Opt] = &[
+    cli::Opt::new("iterations", cli::ValueKind::Usize, "the number of inputs to try for each day")
+        .short('n')
+        .default("10000"),
+    cli::Opt::new("seed", cli::ValueKind::Usize, "the seed for generating inputs. defaults to the current time"),
+    cli::Opt::new("crashes", cli::ValueKind::Path, "the directory that crashing inputs are saved to")
+        .default(fuzz::DEFAULT_DIR),
+    cli::Opt::flag("replay", "only run the saved crashing inputs"),
+    cli::HELP,
+];
+
+/// runs mutated inputs through each day looking for panics
+pub fn fuzz<I>(args: &mut I) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let matches = cli::parse(&[FUZZ_OPTIONS, trace::OPTIONS], args, false)?;
+
+    if matches.flag("help") {
+        print!(
+            "usage: aoc2022 fuzz [options] [day...]\n\n\
+            runs mutated versions of each example through its day looking for\n\
+            inputs that panic instead of ending in an answer or an error. the\n\
+            inputs are saved so that they can be replayed. defaults to every day\n\n{}",
+            cli::help_section("options", FUZZ_OPTIONS)
+        );
+
+        print!("\n{}", cli::help_section("trace options", trace::OPTIONS));
+
+        return Ok(());
+    }
+
+    trace::configure(&matches)?;
+
+    let days = days_from_args(matches.positionals())?;
+    let iterations: usize = matches.require("iterations")?;
+    let crashes_dir: PathBuf = matches.require("crashes")?;
+    let mut crashes: usize = 0;
+
+    if matches.flag("replay") {
+        for (day, path) in fuzz::saved(&crashes_dir)? {
+            let Some(entry) = day::get(day).filter(|entry| days.contains(&entry.day)) else {
+                continue;
+            };
+
+            let input = std::fs::read(&path)?;
+
+            match fuzz::run_one(entry, &input) {
+                Ok(()) => println!("{}: ok", path.display()),
+                Err(message) => {
+                    println!("{}: panic\n  {}", path.display(), message);
+
+                    crashes += 1;
+                }
+            }
+        }
+    } else {
+        let seed = match matches.get::<usize>("seed")? {
+            Some(seed) => seed as u64,
+            None => rng::Rng::seed_from_time(),
+        };
+        let mut rng = rng::Rng::new(seed);
+
+        println!("seed: {}", seed);
+
+        for day in days {
+            let Some(entry) = day::get(day) else {
+                continue;
+            };
+
+            let report = fuzz::fuzz_day(entry, &mut rng, iterations);
+
+            println!("day {}: {} runs, {} crashes", report.day, report.runs, report.crashes.len());
+
+            for crash in &report.crashes {
+                let path = crash.save(&crashes_dir)?;
+
+                println!("  {}\n    {}", path.display(), crash.message);
+            }
+
+            crashes += report.crashes.len();
+        }
+    }
+
+    if crashes > 0 {
+        return Err(error::Error::new(error::ErrorKind::CheckFailed)
+            .with_message(format!("{} input(s) caused a panic", crashes)));
+    }
+
+    Ok(())
+}
+
+/// the options for generating an input
+const GEN_OPTIONS: &[cli::Opt] = &[
+    cli::Opt::new("seed", cli::ValueKind::Usize, "the seed for generating the input. defaults to the current time"),
+    cli::Opt::new("output", cli::ValueKind::Path, "the file to write the input to instead of stdout")
+        .short('o'),
+    cli::Opt::new("name", cli::ValueKind::String, "save the input to the input directory with this name and store its answers. example, real or stress"),
+    cli::Opt::new("answers", cli::ValueKind::Path, "the answers file to store the answers of a named input in")
+        .default("inputs/answers.toml"),
+    cli::Opt::flag("force", "overwrite a named input that already exists"),
+    cli::HELP,
+];
+
+/// writes a synthetic input for the given day along with its answers
+pub fn gen<I>(args: &mut I) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let Some(day) = args.next() else {
+        return Err(error::Error::new(error::ErrorKind::MissingArgument)
+            .with_message("no day specified. usage: aoc2022 gen <day> [options]"));
+    };
+
+    if day == "--help" || day == "-h" {
+        print!(
+            "usage: aoc2022 gen <day> [options]\n\n\
+            writes a valid input for the day built from a seed along with the\n\
+            answers it should give. the answers go to stderr when the input is\n\
+            written to stdout\n\n{}",
+            cli::help_section("options", GEN_OPTIONS)
+        );
+
+        return Ok(());
+    }
+
+    let entry = match day.parse::<u8>() {
+        Ok(num) => gen::get(num),
+        Err(_) => None
+    };
+
+    let Some(entry) = entry else {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!("no generator for day. given: {}", day)));
+    };
+
+    let mut generator = entry.build();
+    let matches = cli::parse(&[GEN_OPTIONS, inputs::OPTIONS, generator.options()], args, false)?;
+
+    if matches.flag("help") {
+        print!(
+            "usage: aoc2022 gen {} [options]\n\n{}",
+            entry.day,
+            cli::help_section("options", GEN_OPTIONS)
+        );
+
+        print!("\n{}", cli::help_section("input options", inputs::OPTIONS));
+
+        if !generator.options().is_empty() {
+            print!("\n{}", cli::help_section("generator options", generator.options()));
+        }
+
+        return Ok(());
+    }
+
+    if let Some(arg) = matches.positionals().first() {
+        return Err(error::build::invalid_argument(arg.clone()));
+    }
+
+    generator.configure(&matches)?;
+
+    let seed = match matches.get::<usize>("seed")? {
+        Some(seed) => seed as u64,
+        None => rng::Rng::seed_from_time(),
+    };
+    let output: Option<PathBuf> = matches.get("output")?;
+    let name: Option<String> = matches.get("name")?;
+
+    if output.is_some() && name.is_some() {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message("only one of output or name can be given"));
+    }
+
+    // the input directory is checked before generating so that an existing
+    // input is not lost by accident
+    let named = match name {
+        Some(name) => {
+            inputs::check_name(&name)?;
+
+            let store = inputs::store(&matches)?;
+            let path = store.path(entry.day, &name);
+
+            if path.exists() && !matches.flag("force") {
+                return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                    .with_message(format!("the input already exists. use --force to overwrite it. {}", path.display())));
+            }
+
+            Some((name, store, path))
+        },
+        None => None,
+    };
+
+    let generated = generator.generate(&mut rng::Rng::new(seed));
+
+    let summary = format!(
+        "day {} seed {}\npart 1: {}\npart 2: {}",
+        entry.day,
+        seed,
+        generated.answers[0],
+        generated.answers[1]
+    );
+
+    if let Some((name, store, path)) = named {
+        let answers_path: PathBuf = matches.require("answers")?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&path, &generated.input)?;
+
+        let mut manifest = store.manifest()?;
+        store.record(&mut manifest, entry.day, &name)?;
+        manifest.save(store.manifest_path())?;
+
+        answers::store(&answers_path, entry.day, &name, &generated.answers)?;
+
+        println!("{}\nwrote {}", summary, path.display());
+    } else if let Some(path) = output {
+        std::fs::write(&path, &generated.input)?;
+
+        println!("{}\nwrote {}", summary, path.display());
+    } else {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(generated.input.as_bytes())?;
+        stdout.flush()?;
+
+        eprintln!("{}", summary);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(args: &[&str]) -> cli::Matches {
+        let mut args = args.iter().map(|arg| arg.to_string());
+
+        cli::parse(&[RUN_OPTIONS, inputs::OPTIONS], &mut args, false).unwrap()
+    }
+
+    #[test]
+    fn piped_input() {
+        // the input directory is not looked at when stdin is piped
+        let missing = std::env::temp_dir().join(format!("aoc2022-piped-{}", std::process::id()));
+        let dir = missing.to_str().unwrap();
+
+        let found = default_inputs(1, &matches(&["--input-dir", dir]), Some(cli::InputSource::Stdin)).unwrap();
+
+        assert!(matches!(found.as_slice(), [cli::InputSource::Stdin]));
+
+        // named inputs are used over stdin
+        assert!(default_inputs(1, &matches(&["--input-dir", dir, "--input", "real"]), Some(cli::InputSource::Stdin)).is_err());
+        assert!(default_inputs(1, &matches(&["--input-dir", dir]), None).is_err());
+    }
+}
@@ -1,149 +1,882 @@
-use std::env::Args;
 use std::io::BufRead;
 
 use crate::error;
+use crate::io;
 use crate::cli;
-
-struct ElfInventory {
-    total: u32,
-    count: u32
+use crate::stats;
+use crate::top_k::TopK;
+use crate::bigint::BigUint;
+use crate::day::{Solution, Part, Answer, Example};
+
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: concat!(
+        "1000\n",
+        "2000\n",
+        "3000\n",
+        "\n",
+        "4000\n",
+        "\n",
+        "5000\n",
+        "6000\n",
+        "\n",
+        "7000\n",
+        "8000\n",
+        "9000\n",
+        "\n",
+        "10000\n",
+    ),
+    answers: ["24000", "45000"],
+};
+
+/// the integer type that the calories are totaled in
+pub trait Calories: Ord + Clone + std::fmt::Display {
+    /// the name used in diagnostics and for the width option
+    const NAME: &'static str;
+
+    fn zero() -> Self;
+
+    /// parses a single item. fails if it is not an integer or does not fit
+    fn parse(value: &str) -> Option<Self>;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn from_u32(value: u32) -> Self;
+
+    /// the closest float for stats
+    fn to_f64(&self) -> f64;
+
+    fn to_answer(&self) -> Answer;
 }
 
-pub fn run(mut args: Args) -> error::Result<()> {
-    let mut file_path: Option<String> = None;
-    let mut total_top_elves: usize = 3;
+macro_rules! primitive_calories {
+    ($($int:ty),*) => {
+        $(
+            impl Calories for $int {
+                const NAME: &'static str = stringify!($int);
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
-        };
+                fn zero() -> Self {
+                    0
+                }
 
-        match arg.as_str() {
-            "-f" | "--file" => {
-                file_path = Some(cli::get_arg_value(&mut args, "file")?);
-            },
-            "--top-elves" => {
-                let v = cli::get_arg_value(&mut args, "top")?;
-
-                total_top_elves = match usize::from_str_radix(&v, 10) {
-                    Ok(i) => i,
-                    Err(_) => {
-                        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
-                            .with_message(format!("top elves value is not a valid usize. value: {}", v)));
+                fn parse(value: &str) -> Option<Self> {
+                    // str::parse takes a leading + which the big integers do not
+                    if value.starts_with('+') {
+                        return None;
+                    }
+
+                    value.parse().ok()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$int>::checked_add(*self, *other)
+                }
+
+                fn from_u32(value: u32) -> Self {
+                    value.into()
+                }
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn to_answer(&self) -> Answer {
+                    match u64::try_from(*self) {
+                        Ok(value) => Answer::Number(value),
+                        Err(_) => Answer::Text(self.to_string()),
                     }
                 }
-            },
-            _ => {
-                return Err(error::build::invalid_argument(arg));
             }
+        )*
+    };
+}
+
+primitive_calories!(u32, u64, u128);
+
+impl Calories for BigUint {
+    const NAME: &'static str = "big";
+
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        BigUint::parse(value)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn from_u32(value: u32) -> Self {
+        BigUint::from(value as u64)
+    }
+
+    fn to_f64(&self) -> f64 {
+        BigUint::to_f64(self)
+    }
+
+    fn to_answer(&self) -> Answer {
+        match self.to_u64() {
+            Some(value) => Answer::Number(value),
+            None => Answer::Text(self.to_string()),
         }
     }
+}
 
-    if total_top_elves == 0 {
-        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
-            .with_message(format!("total top elves is 0")));
+pub struct ElfInventory<C = u32> {
+    pub total: C,
+    pub count: u32
+}
+
+/// the answers for both parts of day 1
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day1Answer<C = u32> {
+    /// the total calories of the elf carrying the most
+    pub most_calories: C,
+    /// the combined calories of the top three elves
+    pub top_three_calories: C,
+}
+
+/// finds the indexs of the elves with the highest totals, ordered from highest
+/// to lowest. elves with the same total are ordered by their index
+pub fn top_elves<C: Calories>(elves: &[ElfInventory<C>], total_top_elves: usize) -> Vec<usize> {
+    let mut top = TopK::new(total_top_elves);
+    top.extend(elves.iter().enumerate().map(|(index, elf)| (&elf.total, index)));
+
+    top.into_sorted_vec()
+        .into_iter()
+        .map(|(_, index)| index)
+        .collect()
+}
+
+/// how lines are split into groups
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// a blank line ends the group
+    Blank,
+    /// a line matching the marker ends the group
+    Marker(String),
+    /// a new group starts when the value of the column changes
+    Key(usize),
+}
+
+/// how the values of a group are combined into its total
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Min,
+    Max,
+    Count,
+}
+
+impl std::str::FromStr for Aggregate {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "sum" => Ok(Aggregate::Sum),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "count" => Ok(Aggregate::Count),
+            _ => Err(()),
+        }
     }
+}
 
-    let reader = cli::get_file_reader(file_path)?;
-    let mut lines = reader.lines();
-    let mut line_count: usize = 0;
+/// the layout of the records in the input. the default is the puzzle input
+/// of one value per line with blank lines between elves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub grouping: Grouping,
+    /// the column that holds the value. when not set the whole line is the
+    /// value
+    pub column: Option<usize>,
+    /// splits the columns of a line. when not set columns are split on
+    /// whitespace
+    pub delimiter: Option<String>,
+    pub aggregate: Aggregate,
+}
 
-    let mut current_index: usize = 0;
-    let mut top_elves: Vec<usize> = Vec::with_capacity(total_top_elves);
-    let mut elves: Vec<ElfInventory> = Vec::with_capacity(10);
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            grouping: Grouping::Blank,
+            column: None,
+            delimiter: None,
+            aggregate: Aggregate::Sum,
+        }
+    }
+}
+
+impl Layout {
+    /// the start of the column in the line along with its text
+    fn field<'a>(&self, line: &'a str, column: usize) -> Option<(usize, &'a str)> {
+        let field = match &self.delimiter {
+            Some(delimiter) => line.split(delimiter.as_str()).nth(column)?,
+            None => line.split_whitespace().nth(column)?,
+        };
+
+        Some((field.as_ptr() as usize - line.as_ptr() as usize, field))
+    }
+
+    /// the column of the line or a diagnostic if the line is too short
+    fn require_field<'a>(&self, line_count: usize, line: &'a str, column: usize) -> error::Result<(usize, &'a str)> {
+        self.field(line, column).ok_or_else(|| error::build::bad_input(error::Diagnostic::new(
+            line_count,
+            line,
+            line.len()..line.len(),
+            format!("expected at least {} columns", column + 1)
+        )))
+    }
+}
+
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<Vec<ElfInventory>> {
+    parse(&io::read_all(reader)?)
+}
+
+pub fn parse(input: &str) -> error::Result<Vec<ElfInventory>> {
+    parse_width(input)
+}
+
+/// parses the elves with the totals in the given integer type
+pub fn parse_width<C: Calories>(input: &str) -> error::Result<Vec<ElfInventory<C>>> {
+    parse_layout(input, &Layout::default())
+}
+
+/// parses the groups of the layout with the totals in the given integer type
+pub fn parse_layout<C: Calories>(input: &str, layout: &Layout) -> error::Result<Vec<ElfInventory<C>>> {
+    let mut elves: Vec<ElfInventory<C>> = Vec::with_capacity(10);
     elves.push(ElfInventory {
-        total: 0,
+        total: C::zero(),
         count: 0
     });
 
-    let start = std::time::Instant::now();
+    let mut last_key: Option<&str> = None;
 
-    loop {
-        line_count += 1;
+    for (line_count, line) in io::StrLines::new(input).normalize(io::Normalize::TRIM) {
+        let ends_group = match &layout.grouping {
+            Grouping::Blank => line.is_empty(),
+            Grouping::Marker(marker) => line == marker,
+            Grouping::Key(_) => false,
+        };
 
-        let line = {
-            let Some(result) = lines.next() else {
-                break;
-            };
+        // repeated separators do not make elves that carry nothing
+        if ends_group {
+            if elves.last().is_some_and(|elf| elf.count > 0) {
+                elves.push(ElfInventory {
+                    total: C::zero(), count: 0
+                });
+            }
 
-            result?
-        };
+            continue;
+        }
 
-        if line.len() == 0 {
-            for index in 0..top_elves.len() {
-                if elves[current_index].total > elves[top_elves[index]].total {
-                    let mut replace = current_index.clone();
+        if line.is_empty() {
+            continue;
+        }
 
-                    for moving in index..top_elves.len() {
-                        replace = std::mem::replace(&mut top_elves[moving], replace);
-                    }
+        if let Grouping::Key(column) = layout.grouping {
+            let (_, key) = layout.require_field(line_count, line, column)?;
 
-                    break;
-                }
+            if last_key.is_some_and(|last| last != key) {
+                elves.push(ElfInventory {
+                    total: C::zero(), count: 0
+                });
             }
 
-            if top_elves.len() < top_elves.capacity() {
-                top_elves.push(current_index.clone());
-            }
+            last_key = Some(key);
+        }
 
-            current_index += 1;
+        let (start, value) = match layout.column {
+            Some(column) => layout.require_field(line_count, line, column)?,
+            None => (0, line),
+        };
 
-            elves.push(ElfInventory {
-                total: 0, count: 0
-            });
+        let Some(calories) = C::parse(value) else {
+            let span = start..start + value.len();
 
-            continue;
-        }
+            return Err(error::build::bad_input(
+                error::Diagnostic::integer(line_count, line, span, C::NAME)
+            ));
+        };
 
-        let Ok(calories) = u32::from_str_radix(&line, 10) else {
-            return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("a line in the file could not be parsed to a u32. line {} \"{}\"", line_count, line)));
+        let current = elves.last_mut().unwrap();
+
+        let total = match layout.aggregate {
+            Aggregate::Sum => current.total.checked_add(&calories),
+            Aggregate::Min if current.count == 0 => Some(calories),
+            Aggregate::Min => Some(current.total.clone().min(calories)),
+            Aggregate::Max => Some(current.total.clone().max(calories)),
+            Aggregate::Count => current.total.checked_add(&C::from_u32(1)),
         };
 
-        if let Some(v) = elves[current_index].total.checked_add(calories) {
-            elves[current_index].total = v;
-            elves[current_index].count += 1;
+        if let Some(v) = total {
+            current.total = v;
+            current.count += 1;
         } else {
+            let span = start..start + value.len();
+
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count,
+                line,
+                span,
+                format!("expected the total calories for the elf to fit in a {}", C::NAME)
+            )));
+        }
+    }
+
+    // a separator at the end of the input
+    if elves.len() > 1 && elves.last().is_some_and(|elf| elf.count == 0) {
+        elves.pop();
+    }
+
+    Ok(elves)
+}
+
+/// the total calories of the elf carrying the most
+pub fn most_calories<C: Calories>(elves: &[ElfInventory<C>]) -> C {
+    top_elves(elves, 1)
+        .first()
+        .map(|index| elves[*index].total.clone())
+        .unwrap_or_else(C::zero)
+}
+
+/// the combined calories of the given number of top elves
+pub fn top_calories<C: Calories>(elves: &[ElfInventory<C>], total_top_elves: usize) -> error::Result<C> {
+    let mut total_calories = C::zero();
+
+    for index in top_elves(elves, total_top_elves) {
+        crate::trace!(
+            Debug,
+            "day1",
+            "elf {} total: {} count: {}",
+            index + 1,
+            elves[index].total,
+            elves[index].count
+        );
+
+        let Some(total) = total_calories.checked_add(&elves[index].total) else {
             return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("the total calories for an elf is larger than a u32. line {} \"{}\"", line_count, line)));
+                .with_message(format!(
+                    "the total calories of the top {} elves does not fit in a {}. use a wider --width",
+                    total_top_elves,
+                    C::NAME
+                )));
+        };
+
+        total_calories = total;
+    }
+
+    Ok(total_calories)
+}
+
+/// the distribution of calories and items across the elves
+pub struct CalorieStats {
+    pub elves: usize,
+    pub totals: stats::Summary,
+    /// the percentile and the total calories at it
+    pub percentiles: Vec<(f64, f64)>,
+    pub items: stats::Summary,
+    /// the index of the first elf with the most items
+    pub most_items: usize,
+    /// the index of the first elf with the fewest items
+    pub fewest_items: usize,
+    pub histogram: Vec<stats::Bin>,
+}
+
+/// the percentiles of the totals that are reported
+const PERCENTILES: &[f64] = &[25.0, 75.0, 90.0, 95.0, 99.0];
+
+/// the longest bar printed in the histogram
+const BAR_WIDTH: usize = 40;
+
+pub fn calorie_stats<C: Calories>(elves: &[ElfInventory<C>], bins: usize) -> Option<CalorieStats> {
+    let mut totals: Vec<f64> = elves.iter().map(|elf| elf.total.to_f64()).collect();
+    let mut items: Vec<f64> = elves.iter().map(|elf| elf.count as f64).collect();
+
+    let histogram = stats::histogram(&totals, bins);
+    let totals_summary = stats::Summary::from_samples(&mut totals)?;
+    let items_summary = stats::Summary::from_samples(&mut items)?;
+
+    let percentiles = PERCENTILES.iter()
+        .map(|pct| (*pct, stats::percentile(&totals, *pct)))
+        .collect();
+
+    // the first elf wins a tie
+    let mut most_items: usize = 0;
+    let mut fewest_items: usize = 0;
+
+    for (index, elf) in elves.iter().enumerate() {
+        if elf.count > elves[most_items].count {
+            most_items = index;
+        }
+
+        if elf.count < elves[fewest_items].count {
+            fewest_items = index;
+        }
+    }
+
+    Some(CalorieStats {
+        elves: elves.len(),
+        totals: totals_summary,
+        percentiles,
+        items: items_summary,
+        most_items,
+        fewest_items,
+        histogram,
+    })
+}
+
+/// whole numbers are shown as is and anything else to two decimal places
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+impl CalorieStats {
+    /// the stats as text. the elves are needed to show the details of the
+    /// elves with the most and fewest items
+    pub fn render<C: Calories>(&self, elves: &[ElfInventory<C>]) -> String {
+        let mut rtn = String::new();
+
+        rtn.push_str(&format!("elves: {}\n", self.elves));
+        rtn.push_str(&format!(
+            "items per elf: min {} max {} mean {}\n",
+            number(self.items.min),
+            number(self.items.max),
+            number(self.items.mean)
+        ));
+
+        rtn.push_str("calories per elf:\n");
+        rtn.push_str(&format!("  min     {}\n", number(self.totals.min)));
+        rtn.push_str(&format!("  mean    {}\n", number(self.totals.mean)));
+        rtn.push_str(&format!("  median  {}\n", number(self.totals.median)));
+        rtn.push_str(&format!("  stddev  {}\n", number(self.totals.stddev)));
+
+        for (pct, value) in &self.percentiles {
+            rtn.push_str(&format!("  {:<7} {}\n", format!("p{}", pct), number(*value)));
+        }
+
+        rtn.push_str(&format!("  max     {}\n", number(self.totals.max)));
+
+        for (label, index) in [("most items", self.most_items), ("fewest items", self.fewest_items)] {
+            rtn.push_str(&format!(
+                "{}: elf {} with {} items and {} calories\n",
+                label,
+                index + 1,
+                elves[index].count,
+                elves[index].total
+            ));
         }
+
+        rtn.push_str("histogram:\n");
+
+        let largest = self.histogram.iter().map(|bin| bin.count).max().unwrap_or(0);
+        let labels: Vec<String> = self.histogram.iter()
+            .map(|bin| format!("{:.0} - {:.0}", bin.lower, bin.upper))
+            .collect();
+        let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+
+        for (bin, label) in self.histogram.iter().zip(labels) {
+            // any bin with a count gets at least one mark
+            let bar = if bin.count == 0 {
+                String::new()
+            } else {
+                format!("{} ", "#".repeat((bin.count * BAR_WIDTH).div_ceil(largest)))
+            };
+
+            rtn.push_str(&format!(
+                "  {:>width$} | {}{}\n",
+                label,
+                bar,
+                bin.count,
+                width = label_width
+            ));
+        }
+
+        rtn
     }
+}
+
+pub fn solve(input: &str) -> error::Result<Day1Answer> {
+    solve_with::<u32>(input)
+}
 
-    for index in 0..top_elves.len() {
-        if elves[current_index].total > elves[top_elves[index]].total {
-            let mut replace = current_index.clone();
+/// the same as [`solve`] with the totals in the given integer type
+pub fn solve_with<C: Calories>(input: &str) -> error::Result<Day1Answer<C>> {
+    let elves = parse_width::<C>(input)?;
 
-            for moving in index..top_elves.len() {
-                replace = std::mem::replace(&mut top_elves[moving], replace);
+    Ok(Day1Answer {
+        most_calories: most_calories(&elves),
+        top_three_calories: top_calories(&elves, 3)?,
+    })
+}
+
+/// the elves parsed at the chosen width
+pub enum Elves {
+    U32(Vec<ElfInventory<u32>>),
+    U64(Vec<ElfInventory<u64>>),
+    U128(Vec<ElfInventory<u128>>),
+    Big(Vec<ElfInventory<BigUint>>),
+}
+
+/// calls the generic expression with the elves of whichever width was parsed
+macro_rules! with_elves {
+    ($elves:expr, $name:ident => $body:expr) => {
+        match $elves {
+            Elves::U32($name) => $body,
+            Elves::U64($name) => $body,
+            Elves::U128($name) => $body,
+            Elves::Big($name) => $body,
+        }
+    };
+}
+
+/// the integer type that the totals are kept in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Width {
+    U32,
+    U64,
+    U128,
+    Big,
+}
+
+impl std::str::FromStr for Width {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "u32" => Ok(Width::U32),
+            "u64" => Ok(Width::U64),
+            "u128" => Ok(Width::U128),
+            "big" => Ok(Width::Big),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Day1 {
+    total_top_elves: usize,
+    width: Width,
+    layout: Layout,
+    stats: bool,
+    bins: usize,
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Day1 {
+            total_top_elves: 3,
+            width: Width::U32,
+            layout: Layout::default(),
+            stats: false,
+            bins: 10,
+        }
+    }
+}
+
+/// a column option given starting at 1 as an index
+fn column_index(matches: &cli::Matches, name: &str) -> error::Result<Option<usize>> {
+    match matches.get::<usize>(name)? {
+        Some(0) => Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!("{} starts at 1", name))),
+        Some(column) => Ok(Some(column - 1)),
+        None => Ok(None),
+    }
+}
+
+impl Solution for Day1 {
+    type Input = Elves;
+
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("top-elves", cli::ValueKind::Usize, "the number of elves with the most calories to total for part 2")
+            .default("3")
+            .changes_answers(),
+        cli::Opt::new("width", cli::ValueKind::Choice(&["u32", "u64", "u128", "big"]), "the integer type to total the calories in")
+            .default("u32")
+            .changes_answers(),
+        cli::Opt::new("group-by", cli::ValueKind::Choice(&["blank", "marker", "key"]), "what separates the groups of values")
+            .default("blank")
+            .changes_answers(),
+        cli::Opt::new("marker", cli::ValueKind::String, "the line that ends a group when grouping by marker")
+            .changes_answers(),
+        cli::Opt::new("key-column", cli::ValueKind::Usize, "the column that starts a new group when it changes when grouping by key. starts at 1")
+            .changes_answers(),
+        cli::Opt::new("column", cli::ValueKind::Usize, "the column that holds the value. starts at 1. the whole line when not given")
+            .changes_answers(),
+        cli::Opt::new("delimiter", cli::ValueKind::String, "splits a line into columns. whitespace when not given")
+            .changes_answers(),
+        cli::Opt::new("aggregate", cli::ValueKind::Choice(&["sum", "min", "max", "count"]), "how the values of a group are combined")
+            .default("sum")
+            .changes_answers(),
+        cli::Opt::flag("stats", "report the distribution of calories and items across the elves"),
+        cli::Opt::new("bins", cli::ValueKind::Usize, "the number of bins in the stats histogram")
+            .default("10"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        let total_top_elves: usize = matches.require("top-elves")?;
+
+        if total_top_elves == 0 {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("total top elves is 0"));
+        }
+
+        let bins: usize = matches.require("bins")?;
+
+        if bins == 0 {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("histogram bins is 0"));
+        }
+
+        let group_by = matches.require::<String>("group-by")?;
+
+        for (option, mode) in [("marker", "marker"), ("key-column", "key")] {
+            if group_by != mode && matches.get::<String>(option)?.is_some() {
+                return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                    .with_message(format!("--{} can only be used with --group-by {}", option, mode)));
             }
+        }
 
-            break;
+        let grouping = match group_by.as_str() {
+            "marker" => {
+                let Some(marker) = matches.get::<String>("marker")? else {
+                    return Err(error::build::missing_value("marker"));
+                };
+
+                Grouping::Marker(marker)
+            },
+            "key" => Grouping::Key(column_index(matches, "key-column")?
+                .ok_or_else(|| error::build::missing_value("key-column"))?),
+            _ => Grouping::Blank,
+        };
+
+        let delimiter: Option<String> = matches.get("delimiter")?;
+
+        if delimiter.as_deref() == Some("") {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message("delimiter is empty"));
         }
+
+        self.layout = Layout {
+            grouping,
+            column: column_index(matches, "column")?,
+            delimiter,
+            aggregate: matches.require("aggregate")?,
+        };
+        self.total_top_elves = total_top_elves;
+        self.width = matches.require("width")?;
+        self.stats = matches.flag("stats");
+        self.bins = bins;
+
+        Ok(())
     }
 
-    if top_elves.len() < top_elves.capacity() {
-        top_elves.push(current_index.clone());
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        let input = io::read_all(reader)?;
+
+        Ok(match self.width {
+            Width::U32 => Elves::U32(parse_layout(&input, &self.layout)?),
+            Width::U64 => Elves::U64(parse_layout(&input, &self.layout)?),
+            Width::U128 => Elves::U128(parse_layout(&input, &self.layout)?),
+            Width::Big => Elves::Big(parse_layout(&input, &self.layout)?),
+        })
     }
 
-    let finish = std::time::Instant::now();
+    fn report(&self, input: &Self::Input) -> error::Result<Option<String>> {
+        if !self.stats {
+            return Ok(None);
+        }
 
-    println!("top elves");
+        Ok(with_elves!(input, elves => calorie_stats(elves, self.bins).map(|stats| stats.render(elves))))
+    }
 
-    let mut total_calories: u64 = 0;
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(with_elves!(input, elves => most_calories(elves).to_answer()))
+    }
 
-    for index in top_elves {
-        println!(
-            "    elf {} total: {} count: {}", 
-            index + 1,
-            elves[index].total,
-            elves[index].count
-        );
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        with_elves!(input, elves => top_calories(elves, self.total_top_elves).map(|total| total.to_answer()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elves(totals: &[u32]) -> Vec<ElfInventory> {
+        totals.iter()
+            .map(|total| ElfInventory { total: *total, count: 1 })
+            .collect()
+    }
+
+    #[test]
+    fn top_elves_ordered() {
+        let elves = elves(&[4, 9, 1, 7, 9]);
+
+        assert_eq!(top_elves(&elves, 3), vec![1, 4, 3]);
+        assert_eq!(top_elves(&elves, 10), vec![1, 4, 3, 0, 2]);
+        assert_eq!(top_elves::<u32>(&[], 3), Vec::<usize>::new());
+        assert_eq!(top_elves(&elves, usize::MAX).len(), 5);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse("100\nabc\n").is_err());
+        assert!(parse("4294967295\n1\n").is_err());
+    }
+
+    #[test]
+    fn widths() {
+        // each elf fits in a u32 but the top three do not
+        let input = "4294967295\n\n4294967295\n\n4294967295\n";
+
+        assert!(solve(input).is_err());
+        assert_eq!(solve_with::<u64>(input).unwrap().top_three_calories, 3 * u32::MAX as u64);
+
+        // a single elf past a u128
+        let input = "340282366920938463463374607431768211455\n1\n\n5\n";
+
+        assert!(parse_width::<u64>(input).is_err());
+        assert!(parse_width::<u128>(input).is_err());
+
+        let answer = solve_with::<BigUint>(input).unwrap();
+
+        assert_eq!(answer.most_calories.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(answer.top_three_calories.to_string(), "340282366920938463463374607431768211461");
+        assert_eq!(answer.most_calories.to_answer(), Answer::Text(answer.most_calories.to_string()));
+    }
+
+    #[test]
+    fn every_width_accepts_the_same_items() {
+        fn totals<C: Calories>(input: &str) -> Option<Vec<String>> {
+            let elves = parse_width::<C>(input).ok()?;
+
+            Some(elves.iter().map(|elf| elf.total.to_answer().to_string()).collect())
+        }
+
+        for input in ["1\n2\n\n3\n", "+5\n", "-5\n", "007\n", "1_000\n", "0x10\n", "1e3\n"] {
+            let expected = totals::<u32>(input);
+
+            assert_eq!(totals::<u64>(input), expected, "{:?}", input);
+            assert_eq!(totals::<u128>(input), expected, "{:?}", input);
+            assert_eq!(totals::<BigUint>(input), expected, "{:?}", input);
+        }
+
+        assert!(totals::<u32>("+5\n").is_none());
+    }
+
+    #[test]
+    fn layouts() {
+        let totals = |elves: Vec<ElfInventory<u32>>| elves.iter().map(|elf| elf.total).collect::<Vec<u32>>();
+
+        let layout = Layout {
+            grouping: Grouping::Marker(String::from("--")),
+            column: Some(1),
+            delimiter: Some(String::from(",")),
+            aggregate: Aggregate::Max,
+        };
+
+        assert_eq!(totals(parse_layout("a,5\nb,7\n--\n\nc,2\n", &layout).unwrap()), vec![7, 2]);
+
+        let layout = Layout {
+            grouping: Grouping::Key(0),
+            column: Some(2),
+            aggregate: Aggregate::Min,
+            ..Layout::default()
+        };
+        let input = "north 1 40\nnorth 2 10\nsouth 3 25\nnorth 4 5\n";
+
+        assert_eq!(totals(parse_layout(input, &layout).unwrap()), vec![10, 25, 5]);
+
+        let layout = Layout { aggregate: Aggregate::Count, ..layout };
+
+        assert_eq!(totals(parse_layout(input, &layout).unwrap()), vec![2, 1, 1]);
+
+        // the value column is missing
+        let layout = Layout { column: Some(3), ..layout };
+
+        assert!(parse_layout::<u32>(input, &layout).is_err());
+    }
+
+    #[test]
+    fn empty_groups() {
+        let counts = |elves: Vec<ElfInventory<u32>>| elves.iter().map(|elf| (elf.total, elf.count)).collect::<Vec<_>>();
+
+        let layout = Layout {
+            grouping: Grouping::Marker(String::from("--")),
+            ..Layout::default()
+        };
+
+        assert_eq!(counts(parse_layout("--\n1\n2\n--\n--\n3\n--\n", &layout).unwrap()), vec![(3, 2), (3, 1)]);
+        assert_eq!(counts(parse("\n1\n\n\n\n2\n3\n\n\n").unwrap()), vec![(1, 1), (5, 2)]);
+
+        let stats = calorie_stats(&parse("1\n\n\n2\n\n").unwrap(), 2).unwrap();
 
-        total_calories += elves[index].total as u64;
+        assert_eq!(stats.elves, 2);
+        assert_eq!(stats.totals.min, 1.0);
+        assert_eq!(stats.items.min, 1.0);
     }
 
-    println!("total of top elves: {}", total_calories);
-    println!("total time: {:#?}", finish.duration_since(start));
+    #[test]
+    fn grouping_options() {
+        let configure = |args: &[&str]| {
+            let mut args = args.iter().map(|arg| arg.to_string());
+            let matches = cli::parse(&[Day1::OPTIONS], &mut args, false).unwrap();
 
-    Ok(())
-}
\ No newline at end of file
+            Day1::default().configure(&matches)
+        };
+
+        assert!(configure(&["--group-by", "marker", "--marker", "--"]).is_ok());
+        assert!(configure(&["--group-by", "key", "--key-column", "1"]).is_ok());
+
+        for args in [
+            &["--marker", "--"][..],
+            &["--group-by", "key", "--key-column", "1", "--marker", "--"],
+            &["--key-column", "1"],
+            &["--group-by", "marker", "--marker", "--", "--key-column", "1"],
+        ] {
+            let err = configure(args).unwrap_err();
+
+            assert!(matches!(err.kind, error::ErrorKind::InvalidArgument), "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let elves = parse(EXAMPLE.input).unwrap();
+        let stats = calorie_stats(&elves, 3).unwrap();
+
+        assert_eq!(stats.elves, 5);
+        assert_eq!(stats.totals.min, 4000.0);
+        assert_eq!(stats.totals.max, 24000.0);
+        assert_eq!(stats.totals.median, 10000.0);
+        assert_eq!(stats.totals.mean, 11000.0);
+        assert_eq!(stats.items.min, 1.0);
+        assert_eq!(stats.items.max, 3.0);
+        assert_eq!(stats.most_items, 0);
+        assert_eq!(stats.fewest_items, 1);
+        assert_eq!(stats.histogram.iter().map(|bin| bin.count).sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn example() {
+        let answer = solve(EXAMPLE.input).unwrap();
+
+        assert_eq!(answer.most_calories, 24000);
+        assert_eq!(answer.top_three_calories, 45000);
+
+        let answer = solve_with::<BigUint>(EXAMPLE.input).unwrap();
+
+        assert_eq!(answer.most_calories, BigUint::from(24000u64));
+        assert_eq!(answer.top_three_calories, BigUint::from(45000u64));
+    }
+}
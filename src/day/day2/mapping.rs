@@ -0,0 +1,131 @@
+use crate::error;
+use crate::toml;
+
+/// a column of codes in the input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// the first column as the move of the opponent
+    Opponent,
+    /// the second column as the move to play
+    Player,
+    /// the second column as the outcome to get
+    Outcome,
+}
+
+impl Column {
+    fn try_from_str(name: &str) -> Option<Column> {
+        match name {
+            "opponent" => Some(Column::Opponent),
+            "player" => Some(Column::Player),
+            "outcome" => Some(Column::Outcome),
+            _ => None
+        }
+    }
+}
+
+impl std::fmt::Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Column::Opponent => f.write_str("opponent"),
+            Column::Player => f.write_str("player"),
+            Column::Outcome => f.write_str("outcome"),
+        }
+    }
+}
+
+/// what the codes of the input mean, replacing the codes in the rules. a
+/// column that is given replaces every code of that column, a column that
+/// is not keeps the codes from the rules
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mapping {
+    /// the column, the code and the name of the move or outcome
+    pub codes: Vec<(Column, String, String)>,
+}
+
+impl Mapping {
+    /// an inline mapping if the value has a `=` otherwise the path to a
+    /// mapping file
+    pub fn load(value: &str) -> error::Result<Mapping> {
+        if value.contains('=') {
+            return Mapping::parse_inline(value);
+        }
+
+        let contents = std::fs::read_to_string(value)
+            .map_err(|err| error::Error::from(err)
+                .with_message(format!("failed to read mapping file \"{}\"", value)))?;
+
+        Mapping::parse(&contents).map_err(|err| err.in_input(value))
+    }
+
+    /// parses a mapping file. each of the `[opponent]`, `[player]` and
+    /// `[outcome]` sections has `code = name` pairs
+    pub fn parse(contents: &str) -> error::Result<Mapping> {
+        let mut rtn = Mapping::default();
+
+        for entry in toml::parse(contents)? {
+            let Some(column) = Column::try_from_str(&entry.section) else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("unknown section in mapping file. line {} \"{}\"", entry.line, entry.section)));
+            };
+
+            rtn.codes.push((column, entry.key, entry.value));
+        }
+
+        Ok(rtn)
+    }
+
+    /// parses a mapping given as `column.code=name` separated by commas.
+    /// "player.X=paper,player.Y=rock,player.Z=scissors"
+    pub fn parse_inline(value: &str) -> error::Result<Mapping> {
+        let mut rtn = Mapping::default();
+
+        for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let parsed = item.split_once('=')
+                .and_then(|(key, name)| {
+                    let (column, code) = key.trim().split_once('.')?;
+
+                    Some((Column::try_from_str(column)?, code.trim(), name.trim()))
+                });
+
+            let Some((column, code, name)) = parsed.filter(|(_, code, name)| !code.is_empty() && !name.is_empty()) else {
+                return Err(error::build::invalid_value("mapping", "<column>.<code>=<name>", item));
+            };
+
+            rtn.codes.push((column, code.to_owned(), name.to_owned()));
+        }
+
+        Ok(rtn)
+    }
+
+    /// if the mapping replaces the codes of the column
+    pub fn has(&self, column: Column) -> bool {
+        self.codes.iter().any(|(given, _, _)| *given == column)
+    }
+
+    /// the codes given for the column along with the name they map to
+    pub fn column(&self, column: Column) -> impl Iterator<Item = (&str, &str)> {
+        self.codes.iter()
+            .filter(move |(given, _, _)| *given == column)
+            .map(|(_, code, name)| (code.as_str(), name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inline_and_file() {
+        let inline = Mapping::parse_inline("player.X=paper, player.Y=rock,outcome.Z=lose").unwrap();
+        let file = Mapping::parse("[player]\nX = paper\nY = rock\n\n[outcome]\nZ = lose\n").unwrap();
+
+        assert_eq!(inline, file);
+        assert!(inline.has(Column::Player));
+        assert!(!inline.has(Column::Opponent));
+        assert_eq!(inline.column(Column::Player).collect::<Vec<_>>(), vec![("X", "paper"), ("Y", "rock")]);
+
+        for invalid in ["player=X", "them.A=rock", "player.=rock", "player.X="] {
+            assert!(Mapping::parse_inline(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
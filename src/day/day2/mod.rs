@@ -0,0 +1,465 @@
+use std::io::BufRead;
+
+use crate::error;
+use crate::io;
+use crate::cli;
+use crate::output::Format;
+use crate::day::{Solution, Part, Answer, Example};
+
+pub mod rules;
+pub mod mapping;
+pub mod report;
+
+pub use rules::{Rules, PlayOutcome};
+pub use mapping::Mapping;
+pub use report::Report;
+
+/// the most moves that every mapping of the second column will be tried for
+const MAX_PERMUTATION_MOVES: usize = 8;
+
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: concat!(
+        "A Y\n",
+        "B X\n",
+        "C Z\n",
+    ),
+    answers: ["15", "12"],
+};
+
+/// a round with the moves as indexs into the rules. the second column is
+/// not always both a move and an outcome when the rules have more moves
+/// than outcomes
+pub struct Round {
+    line: usize,
+    played: usize,
+    recommended: Option<usize>,
+    desired: Option<PlayOutcome>,
+}
+
+/// the answers for both parts of day 2
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day2Answer {
+    /// the total score when the second column is the choice to play
+    pub play_total: u32,
+    /// the total score when the second column is the desired outcome
+    pub outcome_total: u32,
+}
+
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<Vec<Round>> {
+    parse(&io::read_all(reader)?, &Rules::default())
+}
+
+pub fn parse(input: &str, rules: &Rules) -> error::Result<Vec<Round>> {
+    let mut rounds = Vec::new();
+
+    for (line_count, line) in io::StrLines::new(input).normalize(io::Normalize::TRIM) {
+        let Some((played_str, recommended_str)) = line.split_once(' ') else {
+            let span = line.len()..line.len();
+
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count, line, span, "expected a space between the two columns"
+            )));
+        };
+
+        let second_start = played_str.len() + 1;
+
+        let Some(played) = rules.opponent_move(played_str) else {
+            let span = 0..played_str.len();
+
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count, line, span, format!("expected one of {}", rules::one_of(&rules.opponent_codes()))
+            )));
+        };
+
+        let recommended = rules.player_move(recommended_str);
+        let desired = rules.outcome(recommended_str);
+
+        if recommended.is_none() && desired.is_none() {
+            let span = second_start..line.len();
+
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count, line, span, format!("expected one of {}", rules::one_of(&rules.second_codes()))
+            )));
+        }
+
+        rounds.push(Round {
+            line: line_count,
+            played,
+            recommended,
+            desired
+        });
+    }
+
+    Ok(rounds)
+}
+
+/// the second column of a round could not be used for the part
+fn not_usable(round: &Round, usage: &str) -> error::Error {
+    error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("the second column is not {} in the rules. line {}", usage, round.line))
+}
+
+/// the total score when the second column is the choice to play
+pub fn play_total(rules: &Rules, rounds: &[Round]) -> error::Result<u32> {
+    let mut play_total_points: u32 = 0;
+
+    for round in rounds {
+        let Some(recommended) = round.recommended else {
+            return Err(not_usable(round, "a move"));
+        };
+
+        let outcome = rules.play(recommended, round.played);
+
+        crate::trace!(
+            Trace,
+            "day2",
+            "as played  {} vs {} -> {} {} points",
+            rules.name(recommended),
+            rules.name(round.played),
+            outcome,
+            rules.score(recommended, outcome)
+        );
+
+        let Some(total) = play_total_points.checked_add(rules.score(recommended, outcome)) else {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("total score as played is larger than a u32. line {}", round.line)));
+        };
+
+        play_total_points = total;
+    }
+
+    Ok(play_total_points)
+}
+
+/// the total score when the second column is the desired outcome
+pub fn outcome_total(rules: &Rules, rounds: &[Round]) -> error::Result<u32> {
+    let mut outcome_total_points: u32 = 0;
+
+    for round in rounds {
+        let Some(desired) = round.desired else {
+            return Err(not_usable(round, "an outcome"));
+        };
+
+        let Some(recommended) = rules.from_outcome(round.played, desired) else {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!(
+                    "no move gets a {} against {} in the rules. line {}",
+                    desired,
+                    rules.name(round.played),
+                    round.line
+                )));
+        };
+
+        crate::trace!(
+            Trace,
+            "day2",
+            "as outcome {} vs {} -> {} {} points",
+            rules.name(recommended),
+            rules.name(round.played),
+            desired,
+            rules.score(recommended, desired)
+        );
+
+        let Some(total) = outcome_total_points.checked_add(rules.score(recommended, desired)) else {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("total score as outcome is larger than a u32. line {}", round.line)));
+        };
+
+        outcome_total_points = total;
+    }
+
+    Ok(outcome_total_points)
+}
+
+/// a way of mapping the codes of the second column to moves and the part 1
+/// score with it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Permutation {
+    /// the code and the move it maps to
+    pub codes: Vec<(String, usize)>,
+    pub score: u32,
+    /// if this is the mapping of the rules
+    pub current: bool,
+}
+
+/// calls f with every way of picking a different move for each code
+fn each_assignment(codes: usize, moves: usize, picked: &mut Vec<usize>, f: &mut impl FnMut(&[usize])) {
+    if picked.len() == codes {
+        f(picked);
+        return;
+    }
+
+    for mv in 0..moves {
+        if picked.contains(&mv) {
+            continue;
+        }
+
+        picked.push(mv);
+        each_assignment(codes, moves, picked, f);
+        picked.pop();
+    }
+}
+
+/// the part 1 score for every mapping of the second column codes to moves
+/// ordered from the highest score to the lowest. mappings with the same
+/// score keep the order they were tried in
+pub fn permutations(rules: &Rules, rounds: &[Round]) -> error::Result<Vec<Permutation>> {
+    let total_moves = rules.moves().len();
+
+    if total_moves > MAX_PERMUTATION_MOVES {
+        return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!(
+                "there are too many moves to try every mapping. the most is {}",
+                MAX_PERMUTATION_MOVES
+            )));
+    }
+
+    // the moves that have a code in the second column. a code is known by
+    // the move it maps to in the rules
+    let coded: Vec<usize> = (0..total_moves)
+        .filter(|mv| !rules.moves()[*mv].player.is_empty())
+        .collect();
+
+    // how many times each code was played against each move so that a
+    // mapping is scored without going over the rounds again
+    let mut counts = vec![vec![0u32; total_moves]; total_moves];
+
+    for round in rounds {
+        let Some(recommended) = round.recommended else {
+            return Err(not_usable(round, "a move"));
+        };
+
+        let count = &mut counts[recommended][round.played];
+
+        let Some(next) = count.checked_add(1) else {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("count of a move and code is larger than a u32. line {}", round.line)));
+        };
+
+        *count = next;
+    }
+
+    let mut rtn = Vec::new();
+    let mut overflowed = false;
+
+    each_assignment(coded.len(), total_moves, &mut Vec::with_capacity(coded.len()), &mut |picked| {
+        let mut score: u32 = 0;
+
+        for (code_move, mv) in coded.iter().zip(picked) {
+            for (against, count) in counts[*code_move].iter().enumerate() {
+                let points = count.checked_mul(rules.score(*mv, rules.play(*mv, against)))
+                    .and_then(|points| score.checked_add(points));
+
+                let Some(points) = points else {
+                    overflowed = true;
+                    return;
+                };
+
+                score = points;
+            }
+        }
+
+        rtn.push(Permutation {
+            codes: coded.iter()
+                .zip(picked)
+                .map(|(code_move, mv)| (rules.moves()[*code_move].player.clone(), *mv))
+                .collect(),
+            score,
+            current: coded.as_slice() == picked,
+        });
+    });
+
+    if overflowed {
+        return Err(error::Error::new(error::ErrorKind::BadInput)
+            .with_message("total score of a mapping is larger than a u32."));
+    }
+
+    rtn.sort_by_key(|permutation| std::cmp::Reverse(permutation.score));
+
+    Ok(rtn)
+}
+
+/// the text listing of every mapping and its score
+pub fn render_permutations(rules: &Rules, permutations: &[Permutation]) -> String {
+    let mut rtn = format!("second column mappings: {}\n", permutations.len());
+
+    let lines: Vec<String> = permutations.iter()
+        .map(|permutation| permutation.codes.iter()
+            .map(|(code, mv)| format!("{}={}", code, rules.name(*mv)))
+            .collect::<Vec<String>>()
+            .join(" "))
+        .collect();
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+
+    for (permutation, line) in permutations.iter().zip(lines) {
+        rtn.push_str(&format!(
+            "  {:<width$}  {}{}\n",
+            line,
+            permutation.score,
+            if permutation.current { " (current)" } else { "" },
+            width = width
+        ));
+    }
+
+    rtn
+}
+
+pub fn solve(input: &str, rules: &Rules) -> error::Result<Day2Answer> {
+    let rounds = parse(input, rules)?;
+
+    Ok(Day2Answer {
+        play_total: play_total(rules, &rounds)?,
+        outcome_total: outcome_total(rules, &rounds)?,
+    })
+}
+
+#[derive(Default)]
+pub struct Day2 {
+    rules: Rules,
+    permutations: bool,
+    report: Option<Format>,
+}
+
+impl Solution for Day2 {
+    type Input = Vec<Round>;
+
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("rules", cli::ValueKind::String, "the rules to play by. either the path to a rules file or classic or rpsls when there is no such file")
+            .default("classic")
+            .changes_answers(),
+        cli::Opt::new("mapping", cli::ValueKind::String, "what the codes of each column mean. either column.code=name pairs separated by commas or the path to a mapping file")
+            .changes_answers(),
+        cli::Opt::flag("permutations", "report the part 1 score for every mapping of the second column to moves"),
+        cli::Opt::new("report", cli::ValueKind::Choice(&["text", "csv"]), "report every round with the running score, the results of each move and the optimal score"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.rules = Rules::load(&matches.require::<String>("rules")?)?;
+
+        if let Some(value) = matches.get::<String>("mapping")? {
+            self.rules.apply(&Mapping::load(&value)?)?;
+        }
+
+        self.permutations = matches.flag("permutations");
+        self.report = matches.get("report")?;
+
+        Ok(())
+    }
+
+    fn report_format(&self) -> Format {
+        self.report.unwrap_or(Format::Text)
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        parse(&io::read_all(reader)?, &self.rules)
+    }
+
+    fn report(&self, input: &Self::Input) -> error::Result<Option<String>> {
+        let mut rtn = String::new();
+
+        if let Some(format) = self.report {
+            let report = Report::new(&self.rules, input)?;
+
+            if format == Format::Csv {
+                rtn.push_str(&report.render_csv(&self.rules));
+            } else {
+                rtn.push_str(&report.render_text(&self.rules));
+            }
+        }
+
+        if self.permutations {
+            rtn.push_str(&render_permutations(&self.rules, &permutations(&self.rules, input)?));
+        }
+
+        Ok((!rtn.is_empty()).then_some(rtn))
+    }
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(play_total(&self.rules, input)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(outcome_total(&self.rules, input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rpsls() {
+        let rules = Rules::load("rpsls").unwrap();
+
+        // spock against rock is a win. lizard against spock is a win. rock
+        // against paper is a loss
+        let rounds = parse("A Z\nE Y\nB V\n", &rules).unwrap();
+
+        assert_eq!(play_total(&rules, &rounds).unwrap(), 11 + 10 + 1);
+
+        // only X, Y and Z are outcomes
+        let rounds = parse("A V\n", &rules).unwrap();
+
+        assert!(play_total(&rules, &rounds).is_ok());
+        assert!(outcome_total(&rules, &rounds).is_err());
+        assert!(parse("A Q\n", &rules).is_err());
+        assert!(parse("F X\n", &rules).is_err());
+    }
+
+    #[test]
+    fn totals_larger_than_u32() {
+        let rules = Rules::parse(&rules::CLASSIC.replace("points = 6", "points = 2147483647")).unwrap();
+        let rounds = parse("A Y\nA Y\nA Y\nA Z\nA Z\nA Z\n", &rules).unwrap();
+
+        for err in [
+            play_total(&rules, &rounds).unwrap_err(),
+            outcome_total(&rules, &rounds).unwrap_err(),
+            permutations(&rules, &rounds).unwrap_err(),
+        ] {
+            assert_eq!(err.kind, error::ErrorKind::BadInput);
+            assert!(err.message.unwrap().contains("larger than a u32"));
+        }
+    }
+
+    #[test]
+    fn every_mapping() {
+        let rules = Rules::default();
+        let rounds = parse(EXAMPLE.input, &rules).unwrap();
+        let found = permutations(&rules, &rounds).unwrap();
+
+        assert_eq!(found.len(), 6);
+        assert!(found.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        let current: Vec<&Permutation> = found.iter().filter(|permutation| permutation.current).collect();
+
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].score, 15);
+
+        // every mapping scores the same as remapping the input with it
+        for permutation in &found {
+            let spec: Vec<String> = permutation.codes.iter()
+                .map(|(code, mv)| format!("player.{}={}", code, rules.name(*mv)))
+                .collect();
+
+            let mut mapped = Rules::default();
+            mapped.apply(&Mapping::parse_inline(&spec.join(",")).unwrap()).unwrap();
+
+            let rounds = parse(EXAMPLE.input, &mapped).unwrap();
+
+            assert_eq!(play_total(&mapped, &rounds).unwrap(), permutation.score, "{}", spec.join(","));
+        }
+    }
+
+    #[test]
+    fn example() {
+        let answer = solve(EXAMPLE.input, &Rules::default()).unwrap();
+
+        assert_eq!(answer.play_total, 15);
+        assert_eq!(answer.outcome_total, 12);
+    }
+}
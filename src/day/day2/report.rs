@@ -0,0 +1,319 @@
+use crate::error;
+use crate::output;
+use super::{Rules, Round, PlayOutcome};
+
+/// a move made in a round and the running score after it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Play {
+    pub mv: usize,
+    pub outcome: PlayOutcome,
+    pub points: u32,
+    pub total: u32,
+}
+
+/// both ways of reading a round along with the best that could have been
+/// done. an interpretation that the rules cannot use for the round is none
+pub struct RoundReport {
+    pub line: usize,
+    pub opponent: usize,
+    pub played: Option<Play>,
+    pub outcome: Option<Play>,
+    pub optimal: Play,
+}
+
+/// the results of every round where a move was played
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub points: u32,
+}
+
+impl Tally {
+    fn add(&mut self, play: &Play, line: usize) -> error::Result<()> {
+        let count = match play.outcome {
+            PlayOutcome::Win => &mut self.wins,
+            PlayOutcome::Lose => &mut self.losses,
+            PlayOutcome::Draw => &mut self.draws,
+        };
+
+        *count = count.checked_add(1)
+            .ok_or_else(|| too_large("count of a move", line))?;
+        self.points = self.points.checked_add(play.points)
+            .ok_or_else(|| too_large("points of a move", line))?;
+
+        Ok(())
+    }
+}
+
+/// a breakdown of every round for the part 1 and part 2 readings of the
+/// second column. the optimal play wins every round, picking the winning
+/// move with the most points
+pub struct Report {
+    pub rounds: Vec<RoundReport>,
+    /// the tally of each move as played. indexed by move
+    pub played: Vec<Tally>,
+    /// the tally of each move as outcome. indexed by move
+    pub outcome: Vec<Tally>,
+    pub optimal: Vec<Tally>,
+}
+
+fn too_large(what: &str, line: usize) -> error::Error {
+    error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("total {} in the report is larger than a u32. line {}", what, line))
+}
+
+/// the next play after the previous running total
+fn play(rules: &Rules, mv: usize, round: &Round, previous: Option<&Play>) -> error::Result<Play> {
+    let outcome = rules.play(mv, round.played);
+    let points = rules.score(mv, outcome);
+    let total = previous.map(|prev| prev.total)
+        .unwrap_or(0)
+        .checked_add(points)
+        .ok_or_else(|| too_large("score", round.line))?;
+
+    Ok(Play {
+        mv,
+        outcome,
+        points,
+        total,
+    })
+}
+
+impl Report {
+    pub fn new(rules: &Rules, rounds: &[Round]) -> error::Result<Report> {
+        let total_moves = rules.moves().len();
+        let mut rtn = Report {
+            rounds: Vec::with_capacity(rounds.len()),
+            played: vec![Tally::default(); total_moves],
+            outcome: vec![Tally::default(); total_moves],
+            optimal: vec![Tally::default(); total_moves],
+        };
+
+        let mut last_played: Option<Play> = None;
+        let mut last_outcome: Option<Play> = None;
+        let mut last_optimal: Option<Play> = None;
+
+        for round in rounds {
+            let played = round.recommended
+                .map(|mv| play(rules, mv, round, last_played.as_ref()))
+                .transpose()?;
+            let outcome = round.desired
+                .and_then(|desired| rules.from_outcome(round.played, desired))
+                .map(|mv| play(rules, mv, round, last_outcome.as_ref()))
+                .transpose()?;
+
+            // a draw or loss is only taken when no move can win the round
+            let best = [PlayOutcome::Win, PlayOutcome::Draw, PlayOutcome::Lose].into_iter()
+                .find_map(|desired| rules.from_outcome(round.played, desired))
+                .unwrap_or(round.played);
+            let optimal = play(rules, best, round, last_optimal.as_ref())?;
+
+            if let Some(value) = &played {
+                rtn.played[value.mv].add(value, round.line)?;
+                last_played = played;
+            }
+
+            if let Some(value) = &outcome {
+                rtn.outcome[value.mv].add(value, round.line)?;
+                last_outcome = outcome;
+            }
+
+            rtn.optimal[optimal.mv].add(&optimal, round.line)?;
+            last_optimal = Some(optimal);
+
+            rtn.rounds.push(RoundReport {
+                line: round.line,
+                opponent: round.played,
+                played,
+                outcome,
+                optimal,
+            });
+        }
+
+        Ok(rtn)
+    }
+
+    /// the points of every move added up. the running totals were checked
+    /// while building the report so this cannot overflow
+    fn total(tallies: &[Tally]) -> u32 {
+        tallies.iter().fold(0, |total, tally| total.saturating_add(tally.points))
+    }
+
+    pub fn render_text(&self, rules: &Rules) -> String {
+        let cell = |play: &Option<Play>| match play {
+            Some(play) => format!("{} {} {} ({})", rules.name(play.mv), play.outcome, play.points, play.total),
+            None => String::from("-"),
+        };
+
+        let mut rows = vec![[
+            String::from("line"),
+            String::from("opponent"),
+            String::from("as played"),
+            String::from("as outcome"),
+            String::from("optimal"),
+        ]];
+
+        for round in &self.rounds {
+            rows.push([
+                round.line.to_string(),
+                rules.name(round.opponent).to_owned(),
+                cell(&round.played),
+                cell(&round.outcome),
+                cell(&Some(round.optimal)),
+            ]);
+        }
+
+        let mut rtn = format!("rounds: {}\n", self.rounds.len());
+        rtn.push_str(&table(&rows));
+
+        for (label, tallies) in [("as played", &self.played), ("as outcome", &self.outcome), ("optimal", &self.optimal)] {
+            let mut rows = vec![[
+                String::from("move"),
+                String::from("wins"),
+                String::from("losses"),
+                String::from("draws"),
+                String::from("points"),
+            ]];
+
+            for (mv, tally) in tallies.iter().enumerate() {
+                rows.push([
+                    rules.name(mv).to_owned(),
+                    tally.wins.to_string(),
+                    tally.losses.to_string(),
+                    tally.draws.to_string(),
+                    tally.points.to_string(),
+                ]);
+            }
+
+            rtn.push_str(&format!("{} by move. total {}\n", label, Self::total(tallies)));
+            rtn.push_str(&table(&rows));
+        }
+
+        let optimal = Self::total(&self.optimal);
+
+        rtn.push_str(&format!(
+            "optimal: {}. {} more than as played and {} more than as outcome\n",
+            optimal,
+            optimal.saturating_sub(Self::total(&self.played)),
+            optimal.saturating_sub(Self::total(&self.outcome)),
+        ));
+
+        rtn
+    }
+
+    /// the rounds as csv rows followed by the tally of each move after a
+    /// blank line
+    pub fn render_csv(&self, rules: &Rules) -> String {
+        let name = |mv: usize| output::csv_field(rules.name(mv));
+        let cells = |play: &Option<Play>| match play {
+            Some(play) => format!("{},{},{},{}", name(play.mv), play.outcome, play.points, play.total),
+            None => String::from(",,,"),
+        };
+
+        let mut rtn = String::from(concat!(
+            "line,opponent,",
+            "played_move,played_outcome,played_points,played_total,",
+            "outcome_move,outcome_outcome,outcome_points,outcome_total,",
+            "optimal_move,optimal_outcome,optimal_points,optimal_total\n"
+        ));
+
+        for round in &self.rounds {
+            rtn.push_str(&format!(
+                "{},{},{},{},{}\n",
+                round.line,
+                name(round.opponent),
+                cells(&round.played),
+                cells(&round.outcome),
+                cells(&Some(round.optimal))
+            ));
+        }
+
+        rtn.push_str("\nreading,move,wins,losses,draws,points\n");
+
+        for (label, tallies) in [("played", &self.played), ("outcome", &self.outcome), ("optimal", &self.optimal)] {
+            for (mv, tally) in tallies.iter().enumerate() {
+                rtn.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    label,
+                    name(mv),
+                    tally.wins,
+                    tally.losses,
+                    tally.draws,
+                    tally.points
+                ));
+            }
+        }
+
+        rtn
+    }
+}
+
+/// lines up the columns of the rows with two spaces between them
+fn table<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.len());
+        }
+    }
+
+    let mut rtn = String::new();
+
+    for row in rows {
+        let line: Vec<String> = row.iter()
+            .zip(widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect();
+
+        rtn.push_str("  ");
+        rtn.push_str(line.join("  ").trim_end());
+        rtn.push('\n');
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::{parse, EXAMPLE};
+
+    #[test]
+    fn example() {
+        let rules = Rules::default();
+        let rounds = parse(EXAMPLE.input, &rules).unwrap();
+        let report = Report::new(&rules, &rounds).unwrap();
+
+        let played: Vec<u32> = report.rounds.iter().map(|round| round.played.unwrap().total).collect();
+        let outcome: Vec<u32> = report.rounds.iter().map(|round| round.outcome.unwrap().total).collect();
+
+        assert_eq!(played, vec![8, 9, 15]);
+        assert_eq!(outcome, vec![4, 5, 12]);
+
+        // paper beats rock, scissors beats paper and rock beats scissors
+        assert_eq!(report.rounds.last().unwrap().optimal.total, 8 + 9 + 7);
+        assert_eq!(report.optimal.iter().map(|tally| tally.wins).sum::<u32>(), 3);
+
+        let paper = report.played[1];
+
+        assert_eq!((paper.wins, paper.losses, paper.draws, paper.points), (1, 0, 0, 8));
+
+        let csv = report.render_csv(&rules);
+
+        assert_eq!(csv.lines().nth(1), Some("1,rock,paper,Win,8,8,rock,Draw,4,4,paper,Win,8,8"));
+        assert!(report.render_text(&rules).contains("optimal: 24. 9 more than as played and 12 more than as outcome"));
+    }
+
+    #[test]
+    fn larger_than_u32() {
+        let rules = Rules::parse(&super::super::rules::CLASSIC.replace("points = 6", "points = 4294967290")).unwrap();
+        let rounds = parse("A X\nA X\n", &rules).unwrap();
+        let err = Report::new(&rules, &rounds).err().unwrap();
+
+        assert_eq!(err.kind, error::ErrorKind::BadInput);
+        assert!(err.message.unwrap().contains("larger than a u32. line 2"));
+    }
+}
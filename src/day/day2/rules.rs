@@ -0,0 +1,614 @@
+use crate::error;
+use crate::toml;
+use super::mapping::{Mapping, Column};
+
+/// the rules of the puzzle. each shape beats the one before it
+pub const CLASSIC: &str = "\
+# rock paper scissors
+[outcome.win]
+points = 6
+code = Z
+
+[outcome.draw]
+points = 3
+code = Y
+
+[outcome.lose]
+points = 0
+code = X
+
+[move.rock]
+points = 1
+opponent = A
+player = X
+beats = scissors
+
+[move.paper]
+points = 2
+opponent = B
+player = Y
+beats = rock
+
+[move.scissors]
+points = 3
+opponent = C
+player = Z
+beats = paper
+";
+
+/// rock paper scissors lizard spock. every move beats two others and loses
+/// to the other two
+pub const RPSLS: &str = "\
+# rock paper scissors lizard spock
+[outcome.win]
+points = 6
+code = Z
+
+[outcome.draw]
+points = 3
+code = Y
+
+[outcome.lose]
+points = 0
+code = X
+
+[move.rock]
+points = 1
+opponent = A
+player = V
+beats = \"scissors lizard\"
+
+[move.paper]
+points = 2
+opponent = B
+player = W
+beats = \"rock spock\"
+
+[move.scissors]
+points = 3
+opponent = C
+player = X
+beats = \"paper lizard\"
+
+[move.lizard]
+points = 4
+opponent = D
+player = Y
+beats = \"spock paper\"
+
+[move.spock]
+points = 5
+opponent = E
+player = Z
+beats = \"scissors rock\"
+";
+
+/// the rules that can be used by name instead of a file
+pub const BUILTIN: &[(&str, &str)] = &[
+    ("classic", CLASSIC),
+    ("rpsls", RPSLS),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayOutcome {
+    Win,
+    Lose,
+    Draw
+}
+
+impl PlayOutcome {
+    pub const ALL: [PlayOutcome; 3] = [PlayOutcome::Win, PlayOutcome::Draw, PlayOutcome::Lose];
+
+    fn index(&self) -> usize {
+        match self {
+            PlayOutcome::Win => 0,
+            PlayOutcome::Draw => 1,
+            PlayOutcome::Lose => 2,
+        }
+    }
+
+    fn try_from_name(name: &str) -> Option<PlayOutcome> {
+        match name {
+            "win" => Some(PlayOutcome::Win),
+            "draw" => Some(PlayOutcome::Draw),
+            "lose" => Some(PlayOutcome::Lose),
+            _ => None
+        }
+    }
+
+    /// the outcome for the other player
+    pub fn opposite(&self) -> PlayOutcome {
+        match self {
+            PlayOutcome::Win => PlayOutcome::Lose,
+            PlayOutcome::Lose => PlayOutcome::Win,
+            PlayOutcome::Draw => PlayOutcome::Draw,
+        }
+    }
+}
+
+impl std::fmt::Display for PlayOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayOutcome::Win => f.write_str("Win"),
+            PlayOutcome::Lose => f.write_str("Lose"),
+            PlayOutcome::Draw => f.write_str("Draw")
+        }
+    }
+}
+
+/// a move that can be played. moves are referred to by their index in the
+/// rules
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub name: String,
+    pub points: u32,
+    /// the code for the move in the first column
+    pub opponent: String,
+    /// the code for the move in the second column
+    pub player: String,
+}
+
+/// the moves of a game, which moves beat which and how each round is scored
+///
+/// a move wins against any move it beats and loses against any move that
+/// beats it. anything else is a draw.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    moves: Vec<Move>,
+    /// beats[a][b] is true when a beats b
+    beats: Vec<Vec<bool>>,
+    /// indexed by [`PlayOutcome::index`]
+    outcome_points: [u32; 3],
+    outcome_codes: [String; 3],
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::parse(CLASSIC).expect("the classic rules are valid")
+    }
+}
+
+/// the parts of a move while the file is being read
+#[derive(Default)]
+struct MoveEntry {
+    name: String,
+    line: usize,
+    points: Option<u32>,
+    opponent: Option<String>,
+    player: Option<String>,
+    beats: Vec<String>,
+}
+
+fn invalid(line: usize, msg: impl std::fmt::Display) -> error::Error {
+    error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("{} in rules file. line {}", msg, line))
+}
+
+fn points(entry: &toml::Entry) -> error::Result<u32> {
+    entry.value.parse()
+        .map_err(|_| invalid(entry.line, format!("expected an integer for \"{}\"", entry.key)))
+}
+
+/// checks that no two codes in a column are the same
+fn unique_codes<'a>(column: &str, codes: impl Iterator<Item = &'a str>) -> error::Result<()> {
+    let mut seen: Vec<&str> = Vec::new();
+
+    for code in codes.filter(|code| !code.is_empty()) {
+        if seen.contains(&code) {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("the {} code \"{}\" is used more than once in rules file", column, code)));
+        }
+
+        seen.push(code);
+    }
+
+    Ok(())
+}
+
+impl Rules {
+    /// the contents of a rules file or one of the built in rules. a file
+    /// that exists is used even if it has the name of a built in
+    pub fn load(name: &str) -> error::Result<Rules> {
+        if !std::path::Path::new(name).is_file() {
+            if let Some((_, contents)) = BUILTIN.iter().find(|(builtin, _)| *builtin == name) {
+                return Rules::parse(contents);
+            }
+        }
+
+        let contents = std::fs::read_to_string(name)
+            .map_err(|err| error::Error::from(err)
+                .with_message(format!("failed to read rules file \"{}\"", name)))?;
+
+        Rules::parse(&contents).map_err(|err| {
+            let message = err.message.clone().unwrap_or_default();
+
+            err.with_message(format!("{}. file \"{}\"", message, name))
+                .in_input(name)
+        })
+    }
+
+    /// parses a rules file. moves are in `[move.<name>]` sections with the
+    /// `points`, `opponent` and `player` codes and the moves that it `beats`
+    /// separated by spaces. each of the `[outcome.win]`, `[outcome.draw]`
+    /// and `[outcome.lose]` sections has the `points` and `code` used when
+    /// the second column is the outcome
+    pub fn parse(contents: &str) -> error::Result<Rules> {
+        let mut entries: Vec<MoveEntry> = Vec::new();
+        let mut outcome_points: [Option<u32>; 3] = [None; 3];
+        let mut outcome_codes: [Option<String>; 3] = [None, None, None];
+
+        for entry in toml::parse(contents)? {
+            if let Some(name) = entry.section.strip_prefix("move.") {
+                let index = match entries.iter().position(|known| known.name == name) {
+                    Some(index) => index,
+                    None => {
+                        entries.push(MoveEntry {
+                            name: name.to_owned(),
+                            line: entry.line,
+                            ..MoveEntry::default()
+                        });
+
+                        entries.len() - 1
+                    }
+                };
+
+                let current = &mut entries[index];
+
+                match entry.key.as_str() {
+                    "points" => current.points = Some(points(&entry)?),
+                    "opponent" => current.opponent = Some(entry.value),
+                    "player" => current.player = Some(entry.value),
+                    "beats" => current.beats.extend(entry.value.split_whitespace().map(str::to_owned)),
+                    _ => return Err(invalid(entry.line, format!("unknown key \"{}\"", entry.key))),
+                }
+            } else if let Some(outcome) = entry.section.strip_prefix("outcome.").and_then(PlayOutcome::try_from_name) {
+                match entry.key.as_str() {
+                    "points" => outcome_points[outcome.index()] = Some(points(&entry)?),
+                    "code" => outcome_codes[outcome.index()] = Some(entry.value),
+                    _ => return Err(invalid(entry.line, format!("unknown key \"{}\"", entry.key))),
+                }
+            } else {
+                return Err(invalid(entry.line, format!("unknown section \"{}\"", entry.section)));
+            }
+        }
+
+        if entries.is_empty() {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message("there are no moves in rules file"));
+        }
+
+        let mut moves = Vec::with_capacity(entries.len());
+        let mut beats = vec![vec![false; entries.len()]; entries.len()];
+
+        for (index, entry) in entries.iter().enumerate() {
+            let missing = |key: &str| invalid(entry.line, format!("move \"{}\" is missing \"{}\"", entry.name, key));
+
+            for beaten in &entry.beats {
+                let Some(other) = entries.iter().position(|known| known.name == *beaten) else {
+                    return Err(invalid(entry.line, format!("move \"{}\" beats unknown move \"{}\"", entry.name, beaten)));
+                };
+
+                if other == index {
+                    return Err(invalid(entry.line, format!("move \"{}\" beats itself", entry.name)));
+                }
+
+                beats[index][other] = true;
+            }
+
+            moves.push(Move {
+                name: entry.name.clone(),
+                points: entry.points.ok_or_else(|| missing("points"))?,
+                opponent: entry.opponent.clone().ok_or_else(|| missing("opponent"))?,
+                player: entry.player.clone().ok_or_else(|| missing("player"))?,
+            });
+        }
+
+        for (a, entry) in entries.iter().enumerate() {
+            for b in 0..a {
+                if beats[a][b] && beats[b][a] {
+                    return Err(invalid(entry.line, format!("moves \"{}\" and \"{}\" beat each other", moves[a].name, moves[b].name)));
+                }
+            }
+        }
+
+        let mut all_points = [0; 3];
+        let mut all_codes = [String::new(), String::new(), String::new()];
+
+        for outcome in PlayOutcome::ALL {
+            let index = outcome.index();
+            let missing = |key: &str| error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("outcome \"{}\" is missing \"{}\" in rules file", outcome, key));
+
+            all_points[index] = outcome_points[index].ok_or_else(|| missing("points"))?;
+            all_codes[index] = outcome_codes[index].take().ok_or_else(|| missing("code"))?;
+        }
+
+        // the score of a single round has to fit so that only the totals
+        // need to be checked
+        let most_outcome_points = all_points.iter().max().copied().unwrap_or_default();
+
+        for (mv, entry) in moves.iter().zip(&entries) {
+            if mv.points.checked_add(most_outcome_points).is_none() {
+                return Err(invalid(entry.line, format!("the score of move \"{}\" is larger than a u32", mv.name)));
+            }
+        }
+
+        let rules = Rules {
+            moves,
+            beats,
+            outcome_points: all_points,
+            outcome_codes: all_codes,
+        };
+
+        rules.check_codes()?;
+
+        Ok(rules)
+    }
+
+    fn check_codes(&self) -> error::Result<()> {
+        unique_codes("opponent", self.moves.iter().map(|mv| mv.opponent.as_str()))?;
+        unique_codes("player", self.moves.iter().map(|mv| mv.player.as_str()))?;
+        unique_codes("outcome", self.outcome_codes.iter().map(String::as_str))
+    }
+
+    /// replaces the codes of every column in the mapping. a move or outcome
+    /// that is not given a code in a replaced column can no longer be used
+    /// from that column
+    pub fn apply(&mut self, mapping: &Mapping) -> error::Result<()> {
+        for column in [Column::Opponent, Column::Player, Column::Outcome] {
+            if !mapping.has(column) {
+                continue;
+            }
+
+            for mv in self.moves.iter_mut() {
+                match column {
+                    Column::Opponent => mv.opponent.clear(),
+                    Column::Player => mv.player.clear(),
+                    Column::Outcome => {},
+                }
+            }
+
+            if column == Column::Outcome {
+                self.outcome_codes = [String::new(), String::new(), String::new()];
+            }
+
+            for (code, name) in mapping.column(column) {
+                let unknown = || error::Error::new(error::ErrorKind::InvalidArgument)
+                    .with_message(format!("the {} code \"{}\" maps to unknown \"{}\"", column, code, name));
+
+                if column == Column::Outcome {
+                    let outcome = PlayOutcome::try_from_name(name).ok_or_else(unknown)?;
+
+                    self.outcome_codes[outcome.index()] = code.to_owned();
+                    continue;
+                }
+
+                let mv = self.moves.iter_mut()
+                    .find(|mv| mv.name == name)
+                    .ok_or_else(unknown)?;
+
+                let current = if column == Column::Opponent { &mut mv.opponent } else { &mut mv.player };
+
+                if !current.is_empty() {
+                    return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                        .with_message(format!("the {} move \"{}\" is given more than one code", column, name)));
+                }
+
+                *current = code.to_owned();
+            }
+        }
+
+        self.check_codes()
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// the move with the code in the first column
+    pub fn opponent_move(&self, code: &str) -> Option<usize> {
+        self.moves.iter().position(|mv| !mv.opponent.is_empty() && mv.opponent == code)
+    }
+
+    /// the move with the code in the second column
+    pub fn player_move(&self, code: &str) -> Option<usize> {
+        self.moves.iter().position(|mv| !mv.player.is_empty() && mv.player == code)
+    }
+
+    /// the outcome with the code in the second column
+    pub fn outcome(&self, code: &str) -> Option<PlayOutcome> {
+        PlayOutcome::ALL.into_iter()
+            .find(|outcome| !code.is_empty() && self.outcome_codes[outcome.index()] == code)
+    }
+
+    pub fn opponent_codes(&self) -> Vec<&str> {
+        self.moves.iter()
+            .map(|mv| mv.opponent.as_str())
+            .filter(|code| !code.is_empty())
+            .collect()
+    }
+
+    /// the codes in the second column that are moves
+    pub fn player_codes(&self) -> Vec<&str> {
+        self.moves.iter()
+            .map(|mv| mv.player.as_str())
+            .filter(|code| !code.is_empty())
+            .collect()
+    }
+
+    /// every code that can be in the second column
+    pub fn second_codes(&self) -> Vec<&str> {
+        let mut codes = self.player_codes();
+
+        for code in &self.outcome_codes {
+            if !code.is_empty() && !codes.contains(&code.as_str()) {
+                codes.push(code);
+            }
+        }
+
+        codes
+    }
+
+    pub fn name(&self, mv: usize) -> &str {
+        &self.moves[mv].name
+    }
+
+    /// the outcome of playing the move against the other
+    pub fn play(&self, mv: usize, against: usize) -> PlayOutcome {
+        if self.beats[mv][against] {
+            PlayOutcome::Win
+        } else if self.beats[against][mv] {
+            PlayOutcome::Lose
+        } else {
+            PlayOutcome::Draw
+        }
+    }
+
+    /// the move that gets the desired outcome against the other. when more
+    /// than one move does the one with the most points is picked, then the
+    /// first one listed
+    pub fn from_outcome(&self, against: usize, desired: PlayOutcome) -> Option<usize> {
+        let mut rtn: Option<usize> = None;
+
+        for mv in 0..self.moves.len() {
+            if self.play(mv, against) != desired {
+                continue;
+            }
+
+            if rtn.is_none_or(|best| self.moves[mv].points > self.moves[best].points) {
+                rtn = Some(mv);
+            }
+        }
+
+        rtn
+    }
+
+    /// the score of a round for the player
+    pub fn score(&self, mv: usize, outcome: PlayOutcome) -> u32 {
+        self.moves[mv].points + self.outcome_points[outcome.index()]
+    }
+}
+
+/// the codes listed for a diagnostic. "A, B or C"
+pub fn one_of(codes: &[&str]) -> String {
+    match codes.split_last() {
+        None => String::new(),
+        Some((last, [])) => (*last).to_owned(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_rules() -> Vec<Rules> {
+        BUILTIN.iter().map(|(_, contents)| Rules::parse(contents).unwrap()).collect()
+    }
+
+    #[test]
+    fn classic() {
+        let rules = Rules::default();
+        let [rock, paper, scissors] = [0, 1, 2];
+
+        assert_eq!(rules.play(rock, scissors), PlayOutcome::Win);
+        assert_eq!(rules.play(paper, rock), PlayOutcome::Win);
+        assert_eq!(rules.play(scissors, paper), PlayOutcome::Win);
+        assert_eq!(rules.play(rock, paper), PlayOutcome::Lose);
+        assert_eq!(rules.play(paper, paper), PlayOutcome::Draw);
+        assert_eq!(rules.moves().iter().map(|mv| mv.points).collect::<Vec<u32>>(), vec![1, 2, 3]);
+        assert_eq!(one_of(&rules.opponent_codes()), "A, B or C");
+    }
+
+    #[test]
+    fn play_is_a_draw_only_against_itself() {
+        for rules in all_rules() {
+            for mv in 0..rules.moves().len() {
+                for against in 0..rules.moves().len() {
+                    assert_eq!(
+                        rules.play(mv, against) == PlayOutcome::Draw,
+                        mv == against,
+                        "{} against {}", rules.name(mv), rules.name(against)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn play_is_opposite_for_the_other_player() {
+        for rules in all_rules() {
+            for mv in 0..rules.moves().len() {
+                for against in 0..rules.moves().len() {
+                    assert_eq!(
+                        rules.play(against, mv),
+                        rules.play(mv, against).opposite(),
+                        "{} against {}", rules.name(against), rules.name(mv)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_outcome_then_play_gives_desired() {
+        for rules in all_rules() {
+            for against in 0..rules.moves().len() {
+                for desired in PlayOutcome::ALL {
+                    let mv = rules.from_outcome(against, desired).unwrap();
+
+                    assert_eq!(rules.play(mv, against), desired, "{} against {}", desired, rules.name(against));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mapping() {
+        let mut rules = Rules::default();
+        rules.apply(&Mapping::parse_inline("player.X=paper,player.Y=rock,outcome.Q=win").unwrap()).unwrap();
+
+        assert_eq!(rules.player_move("X"), Some(1));
+        assert_eq!(rules.player_move("Y"), Some(0));
+        assert_eq!(rules.player_move("Z"), None);
+        assert_eq!(rules.opponent_move("A"), Some(0));
+        assert_eq!(rules.outcome("Q"), Some(PlayOutcome::Win));
+        assert_eq!(rules.outcome("Z"), None);
+        assert_eq!(rules.second_codes(), vec!["Y", "X", "Q"]);
+
+        for invalid in ["player.X=stone", "player.X=rock,player.Y=rock", "opponent.A=rock,opponent.A=paper"] {
+            assert!(Rules::default().apply(&Mapping::parse_inline(invalid).unwrap()).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn load() {
+        assert_eq!(Rules::load("classic").unwrap().name(2), "scissors");
+
+        let path = std::env::temp_dir().join(format!("aoc2022-rules-{}.toml", std::process::id()));
+
+        std::fs::write(&path, CLASSIC.replace("points = 2\n", "points = two\n")).unwrap();
+
+        let err = Rules::load(path.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        let message = err.message.unwrap();
+
+        assert!(message.contains("expected an integer"), "{}", message);
+        assert!(message.contains(path.to_str().unwrap()), "{}", message);
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let mutual = CLASSIC.replace("beats = rock", "beats = \"rock scissors\"");
+        let unknown = CLASSIC.replace("beats = rock", "beats = stone");
+        let duplicate = CLASSIC.replace("opponent = B", "opponent = A");
+        let missing = CLASSIC.replace("points = 2\n", "");
+
+        let too_large = CLASSIC.replace("points = 6", "points = 4294967295");
+
+        for contents in [mutual, unknown, duplicate, missing, too_large, String::from("[outcome.win]\npoints = 6\n")] {
+            assert!(Rules::parse(&contents).is_err(), "{}", contents);
+        }
+    }
+}
@@ -1,16 +1,29 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::env::Args;
 use std::io::BufRead;
 
 use crate::error;
-use crate::cli;
+use crate::io;
+use crate::day::{Solution, Part, Answer, Example};
+
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: concat!(
+        "vJrwpWtwJgWrhcsFMMfFFhFp\n",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n",
+        "PmmdzqPrVvPwwTWBwg\n",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n",
+        "ttgJtRGJQctTZtZT\n",
+        "CrZsJsPPZsGzwwsLwLmpwMDw\n",
+    ),
+    answers: ["157", "70"],
+};
 
 fn get_item_value(ch_int: &u32) -> Option<u32> {
-    if *ch_int >= 0x61 && *ch_int <= 0x7a {
+    if (0x61..=0x7a).contains(ch_int) {
         // lower case letter
         Some(*ch_int - 0x60)
-    } else if *ch_int >= 0x41 && *ch_int <= 0x5a {
+    } else if (0x41..=0x5a).contains(ch_int) {
         // upper case letter
         Some(*ch_int - 0x26)
     } else {
@@ -28,132 +41,151 @@ fn _get_char_value(item: &u32) -> u32 {
     }
 }
 
-pub fn run(mut args: Args) -> error::Result<()> {
-    let mut file_path: Option<String> = None;
+/// the answers for both parts of day 3
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day3Answer {
+    /// the sum of the priorities of the items in both compartments
+    pub priority_total: u32,
+    /// the sum of the priorities of the badges for each group of three
+    pub badge_total: u32,
+}
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
-        };
+/// the validated puzzle input. the rucksacks are borrowed from the buffer
+/// instead of stored individually
+pub struct Rucksacks {
+    data: String,
+}
 
-        match arg.as_str() {
-            "-f" | "--file" => {
-                file_path = Some(cli::get_arg_value(&mut args, "file")?);
-            },
-            _ => {
-                return Err(error::build::invalid_argument(arg));
-            }
-        }
+impl Rucksacks {
+    /// the contents of each rucksack
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        io::StrLines::new(&self.data).normalize(io::Normalize::TRIM).map(|(_, line)| line)
     }
+}
 
-    let reader = cli::get_file_reader(file_path)?;
-    let mut lines = reader.lines();
-    let mut line_count: usize = 0;
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<Rucksacks> {
+    validate(io::read_all(reader)?)
+}
 
-    let mut total: u32 = 0;
-    let mut badge_total: u32 = 0;
-    let mut inventory_group: HashMap<u32, u8> = HashMap::new();
-    let mut flag_id: u8 = 0b001;
+pub fn parse(input: &str) -> error::Result<Rucksacks> {
+    validate(input.to_owned())
+}
 
-    let start = std::time::Instant::now();
+/// checks that every line of the buffer is a valid rucksack
+fn validate(data: String) -> error::Result<Rucksacks> {
+    for (line_count, line) in io::StrLines::new(&data).normalize(io::Normalize::TRIM) {
+        if line.is_empty() {
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count, line, 0..0, "expected at least one item"
+            )));
+        }
 
-    loop {
-        line_count += 1;
+        for (index, ch) in line.char_indices() {
+            if get_item_value(&ch.into()).is_none() {
+                let span = index..(index + ch.len_utf8());
 
-        let line = {
-            let Some(result) = lines.next() else {
-                break;
-            };
+                return Err(error::build::bad_input(error::Diagnostic::new(
+                    line_count, line, span, "expected an item from a-z or A-Z"
+                )));
+            }
+        }
+    }
 
-            result?
-        };
+    Ok(Rucksacks { data })
+}
 
-        if line.len() == 0 {
-            return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("a line in the file has no characters. line {}", line_count)));
-        }
+fn invalid_item(line_count: usize, line: &str) -> error::Error {
+    error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("rucksack has an item that is not from a-z or A-Z. line {} \"{}\"", line_count, line))
+}
 
-        if cfg!(debug_assertions) {
-            println!("inventory: {} {}\nflag_id: {}", line, line.len(), flag_id);
-        }
+/// the sum of the priorities of the items that are in both compartments of a
+/// rucksack
+pub fn priority_total<'a, I>(input: I) -> error::Result<u32>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    let mut total: u32 = 0;
+
+    for (index, line) in input.into_iter().enumerate() {
+        crate::trace!(Trace, "day3", "inventory: {} {}", line, line.len());
 
-        let (comp_one, comp_two) = line.split_at(line.len() / 2);
-        let mut comp_one_set: HashSet<u32> = HashSet::with_capacity(comp_one.len());
+        // a line that did not come from validate could have a multibyte
+        // char in the middle
+        let Some((comp_one, comp_two)) = line.split_at_checked(line.len() / 2) else {
+            return Err(invalid_item(index + 1, line));
+        };
+        let comp_one_set: HashSet<u32> = comp_one.chars()
+            .map(u32::from)
+            .collect();
         let mut comp_two_set: HashSet<u32> = HashSet::with_capacity(comp_two.len());
 
-        if cfg!(debug_assertions) {
-            println!("    comp one: {}\n    comp two: {}", comp_one, comp_two);
-        }
+        crate::trace!(Trace, "day3", "comp one: {} comp two: {}", comp_one, comp_two);
 
-        for ch in comp_one.chars() {
+        for ch in comp_two.chars() {
             let ch_int: u32 = ch.into();
+            let Some(item_value) = get_item_value(&ch_int) else {
+                return Err(invalid_item(index + 1, line));
+            };
+
+            if !comp_two_set.insert(ch_int) {
+                continue;
+            }
 
-            if let Some(_) = get_item_value(&ch_int) {
-                if let Some(ids) = inventory_group.get_mut(&ch_int) {
-                    *ids |= flag_id;
+            if comp_one_set.contains(&ch_int) {
+                crate::trace!(Debug, "day3", "duplicate item found: {} {}", ch, item_value);
+
+                if let Some(v) = total.checked_add(item_value) {
+                    total = v;
                 } else {
-                    inventory_group.insert(ch_int, flag_id);
+                    return Err(error::Error::new(error::ErrorKind::BadInput)
+                        .with_message(format!("total count of duplicate items is larger than a u32. line {} \"{}\"", index + 1, line)))
                 }
-
-                comp_one_set.insert(ch_int);
-            } else {
-                return Err(error::Error::new(error::ErrorKind::BadInput)
-                    .with_message(format!("a line in the file contains invalid characters. line {} \"{}\"", line_count, line)));
             }
         }
 
-        for ch in comp_two.chars() {
-            let ch_int: u32 = ch.into();
+        crate::trace!(Trace, "day3", "current total: {}", total);
+    }
 
-            if let Some(item_value) = get_item_value(&ch_int) {
-                if let Some(ids) = inventory_group.get_mut(&ch_int) {
-                    *ids |= flag_id;
-                } else {
-                    inventory_group.insert(ch_int, flag_id);
-                }
+    Ok(total)
+}
 
-                if !comp_two_set.insert(ch_int) {
-                    continue;
-                }
+/// the sum of the priorities of the items shared by each group of three
+/// rucksacks
+pub fn badge_total<'a, I>(input: I) -> error::Result<u32>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    let mut badge_total: u32 = 0;
+    let mut inventory_group: HashMap<u32, u8> = HashMap::new();
+    let mut flag_id: u8 = 0b001;
 
-                if comp_one_set.contains(&ch_int) {
-                    if cfg!(debug_assertions) {
-                        println!("    duplicate item found: {} {}", ch, item_value);
-                    }
+    for (index, line) in input.into_iter().enumerate() {
+        crate::trace!(Trace, "day3", "inventory: {} {} flag_id: {:03b}", line, line.len(), flag_id);
 
-                    if let Some(v) = total.checked_add(item_value) {
-                        total = v;
-                    } else {
-                        return Err(error::Error::new(error::ErrorKind::BadInput)
-                            .with_message(format!("total count of duplicate items is larger than a u32. line {} \"{}\"", line_count, line)))
-                    }
-                }
-            } else {
-                return Err(error::Error::new(error::ErrorKind::BadInput)
-                    .with_message(format!("a line in the file contains invalid characters. line {} \"{}\"", line_count, line)));
+        for ch in line.chars() {
+            if get_item_value(&ch.into()).is_none() {
+                return Err(invalid_item(index + 1, line));
             }
-        }
 
-        if cfg!(debug_assertions) {
-            println!("current total: {}", total);
+            if let Some(ids) = inventory_group.get_mut(&ch.into()) {
+                *ids |= flag_id;
+            } else {
+                inventory_group.insert(ch.into(), flag_id);
+            }
         }
 
         if flag_id == 0b100 {
-            if cfg!(debug_assertions) {
-                println!("checking group inventory")
-            }
+            crate::trace!(Trace, "day3", "checking group inventory");
 
             for (key, value) in &inventory_group {
-                if cfg!(debug_assertions) {
-                    println!("    item: {} ids: {:03b}", char::try_from(key.clone()).unwrap(), value);
-                }
+                crate::trace!(Trace, "day3", "item: {} ids: {:03b}", char::try_from(*key).unwrap(), value);
 
                 if *value == 0b111 {
-                    let item_value = get_item_value(key).unwrap();
+                    // every char was checked when it was added
+                    let item_value = get_item_value(key).unwrap_or(0);
 
-                    if cfg!(debug_assertions) {
-                        println!("    duplicate item found: {} {}", char::try_from(key.clone()).unwrap(), item_value);
-                    }
+                    crate::trace!(Debug, "day3", "badge found: {} {}", char::try_from(*key).unwrap(), item_value);
 
                     if let Some(v) = badge_total.checked_add(item_value) {
                         badge_total = v;
@@ -171,38 +203,93 @@ pub fn run(mut args: Args) -> error::Result<()> {
         }
     }
 
-    if flag_id == 0b100 {
-        if cfg!(debug_assertions) {
-            println!("checking group inventory")
+    Ok(badge_total)
+}
+
+pub fn solve(input: &str) -> error::Result<Day3Answer> {
+    let rucksacks = parse(input)?;
+
+    Ok(Day3Answer {
+        priority_total: priority_total(rucksacks.iter())?,
+        badge_total: badge_total(rucksacks.iter())?,
+    })
+}
+
+#[derive(Default)]
+pub struct Day3 {}
+
+impl Solution for Day3 {
+    type Input = Rucksacks;
+
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        parse_reader(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(priority_total(input.iter())?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(badge_total(input.iter())?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn item_values() {
+        assert_eq!(get_item_value(&('a' as u32)), Some(1));
+        assert_eq!(get_item_value(&('z' as u32)), Some(26));
+        assert_eq!(get_item_value(&('A' as u32)), Some(27));
+        assert_eq!(get_item_value(&('Z' as u32)), Some(52));
+        assert_eq!(get_item_value(&('0' as u32)), None);
+        assert_eq!(get_item_value(&('é' as u32)), None);
+    }
+
+    #[test]
+    fn item_values_only_for_letters() {
+        for ch in (0..=0x2ff).filter_map(char::from_u32) {
+            assert_eq!(
+                get_item_value(&(ch as u32)).is_some(),
+                ch.is_ascii_alphabetic(),
+                "{:?}", ch
+            );
         }
+    }
 
-        for (key, value) in inventory_group {
-            if cfg!(debug_assertions) {
-                println!("    item: {} ids: {:03b}", char::try_from(key.clone()).unwrap(), value);
-            }
+    #[test]
+    fn item_values_are_unique_and_reversible() {
+        let mut seen = [false; 53];
 
-            if value == 0b111 {
-                let item_value = get_item_value(&key).unwrap();
+        for ch in ('a'..='z').chain('A'..='Z') {
+            let value = get_item_value(&(ch as u32)).unwrap();
 
-                if cfg!(debug_assertions) {
-                    println!("    duplicate item found: {} {}", char::try_from(key).unwrap(), item_value);
-                }
+            assert!((1..=52).contains(&value), "{:?} has value {}", ch, value);
+            assert!(!seen[value as usize], "{:?} has a duplicate value {}", ch, value);
+            assert_eq!(_get_char_value(&value), ch as u32);
 
-                if let Some(v) = badge_total.checked_add(item_value) {
-                    badge_total = v;
-                } else {
-                    return Err(error::Error::new(error::ErrorKind::BadInput)
-                        .with_message("total count of badge is larger than a u32."))
-                }
-            }
+            seen[value as usize] = true;
         }
     }
 
-    let finish = std::time::Instant::now();
+    #[test]
+    fn unvalidated_items() {
+        assert!(priority_total(["aéb"]).is_err());
+        assert!(priority_total(["ab1a"]).is_err());
+        assert!(badge_total(["a", "a", "a1"]).is_err());
+    }
 
-    println!("total: {}", total);
-    println!("badge total: {}", badge_total);
-    println!("total time: {:#?}", finish.duration_since(start));
+    #[test]
+    fn example() {
+        let answer = solve(EXAMPLE.input).unwrap();
 
-    Ok(())
-}
\ No newline at end of file
+        assert_eq!(answer.priority_total, 157);
+        assert_eq!(answer.badge_total, 70);
+    }
+}
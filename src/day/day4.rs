@@ -1,19 +1,62 @@
-use std::env::Args;
 use std::io::BufRead;
 
 use crate::error;
-use crate::cli;
+use crate::io;
+use crate::day::{Solution, Part, Answer, Example};
 
-struct Range {
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: concat!(
+        "2-4,6-8\n",
+        "2-3,4-5\n",
+        "5-7,7-9\n",
+        "2-8,3-7\n",
+        "6-6,4-6\n",
+        "2-6,4-8\n",
+    ),
+    answers: ["2", "4"],
+};
+
+pub struct Range {
     lower: u32,
     upper: u32
 }
 
+/// why a range could not be parsed. each variant holds the byte span of the
+/// problem in the parsed string
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 enum FromStrError {
-    InvalidFormat,
-    InvalidInteger,
-    InvalidLowerBound,
-    InvalidUpperBound
+    InvalidFormat(std::ops::Range<usize>),
+    InvalidInteger(std::ops::Range<usize>),
+    InvalidLowerBound(std::ops::Range<usize>),
+}
+
+impl FromStrError {
+    /// creates a diagnostic for the line that the range was in. offset is
+    /// where the parsed string starts in the line
+    fn into_diagnostic(self, line_number: usize, line: &str, offset: usize) -> error::Diagnostic {
+        match self {
+            FromStrError::InvalidFormat(span) => error::Diagnostic::new(
+                line_number,
+                line,
+                (span.start + offset)..(span.end + offset),
+                "expected a range formatted as lower-upper"
+            ),
+            FromStrError::InvalidInteger(span) => error::Diagnostic::integer(
+                line_number,
+                line,
+                (span.start + offset)..(span.end + offset),
+                "u32"
+            ),
+            FromStrError::InvalidLowerBound(span) => error::Diagnostic::new(
+                line_number,
+                line,
+                (span.start + offset)..(span.end + offset),
+                "expected the lower bound to be less than or equal to the upper bound"
+            ),
+        }
+    }
 }
 
 impl Range {
@@ -23,20 +66,20 @@ impl Range {
     {
         let str_ref = string.as_ref();
         let Some((lower_str, upper_str)) = str_ref.split_once('-') else {
-            return Err(FromStrError::InvalidFormat);
+            return Err(FromStrError::InvalidFormat(str_ref.len()..str_ref.len()));
         };
 
-        let Ok(lower) = u32::from_str_radix(lower_str, 10) else {
-            return Err(FromStrError::InvalidInteger);
+        let upper_start = lower_str.len() + 1;
+
+        let Ok(lower) = lower_str.parse::<u32>() else {
+            return Err(FromStrError::InvalidInteger(0..lower_str.len()));
         };
-        let Ok(upper) = u32::from_str_radix(upper_str, 10) else {
-            return Err(FromStrError::InvalidInteger);
+        let Ok(upper) = upper_str.parse::<u32>() else {
+            return Err(FromStrError::InvalidInteger(upper_start..str_ref.len()));
         };
 
         if lower > upper {
-            Err(FromStrError::InvalidLowerBound)
-        } else if upper < lower {
-            Err(FromStrError::InvalidUpperBound)
+            Err(FromStrError::InvalidLowerBound(0..lower_str.len()))
         } else {
             Ok(Range { lower, upper })
         }
@@ -47,77 +90,201 @@ impl Range {
     }
 
     fn overlaps(&self, check: &Self) -> bool {
-        (self.lower >= check.lower && self.lower <= check.upper) ||
-        (self.upper <= check.lower && self.upper >= check.upper)
+        self.lower <= check.upper && check.lower <= self.upper
     }
 }
 
-pub fn run(mut args: Args) -> error::Result<()> {
-    let mut file_path: Option<String> = None;
+/// the answers for both parts of day 4
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day4Answer {
+    /// the number of pairs where one range fully contains the other
+    pub contained: u32,
+    /// the number of pairs where the ranges overlap at all
+    pub overlapping: u32,
+}
+
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<Vec<(Range, Range)>> {
+    parse(&io::read_all(reader)?)
+}
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
+pub fn parse(input: &str) -> error::Result<Vec<(Range, Range)>> {
+    let mut pairs = Vec::new();
+
+    for (line_count, line) in io::StrLines::new(input).normalize(io::Normalize::TRIM) {
+        let Some((first_pair, second_pair)) = line.split_once(',') else {
+            let span = line.len()..line.len();
+
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_count, line, span, "expected a ',' between the two ranges"
+            )));
         };
 
-        match arg.as_str() {
-             "-f" | "--file" => {
-                file_path = Some(cli::get_arg_value(&mut args, "file")?);
-             },
-             _ => {
-                return Err(error::build::invalid_argument(arg))
-             }
-        }
+        let first_range = match Range::from_str(first_pair) {
+            Ok(range) => range,
+            Err(err) => {
+                return Err(error::build::bad_input(err.into_diagnostic(line_count, line, 0)));
+            }
+        };
+        let second_range = match Range::from_str(second_pair) {
+            Ok(range) => range,
+            Err(err) => {
+                let offset = first_pair.len() + 1;
+
+                return Err(error::build::bad_input(err.into_diagnostic(line_count, line, offset)));
+            }
+        };
+
+        pairs.push((first_range, second_range));
     }
 
-    let reader = cli::get_file_reader(file_path)?;
-    let mut lines = reader.lines();
-    let mut line_count: usize = 0;
+    Ok(pairs)
+}
 
+/// the number of pairs where one range fully contains the other
+pub fn contained_pairs(input: &[(Range, Range)]) -> u32 {
     let mut total_contained_pairs: u32 = 0;
+
+    for (first_range, second_range) in input {
+        if first_range.contains(second_range) || second_range.contains(first_range) {
+            total_contained_pairs += 1;
+        }
+    }
+
+    total_contained_pairs
+}
+
+/// the number of pairs where the ranges overlap
+pub fn overlapping_pairs(input: &[(Range, Range)]) -> u32 {
     let mut total_overlapping_pairs: u32 = 0;
 
-    let start = std::time::Instant::now();
+    for (first_range, second_range) in input {
+        if first_range.overlaps(second_range) {
+            total_overlapping_pairs += 1;
+        }
+    }
 
-    loop {
-        line_count += 1;
+    total_overlapping_pairs
+}
 
-        let line = {
-            let Some(result) = lines.next() else {
-                break;
-            };
+pub fn solve(input: &str) -> error::Result<Day4Answer> {
+    let pairs = parse(input)?;
 
-            result?
-        };
+    Ok(Day4Answer {
+        contained: contained_pairs(&pairs),
+        overlapping: overlapping_pairs(&pairs),
+    })
+}
 
-        let Some((first_pair, second_pair)) = line.split_once(',') else {
-            return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("a line in the file is not formatted properly. line {} \"{}\"", line_count, line)));
-        };
+#[derive(Default)]
+pub struct Day4 {}
 
-        let Ok(first_range) = Range::from_str(first_pair) else {
-            return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("a line in the file is not formatted properly. line {} \"{}\"", line_count, line)))
-        };
-        let Ok(second_range) = Range::from_str(second_pair) else {
-            return Err(error::Error::new(error::ErrorKind::BadInput)
-                .with_message(format!("a line in the file is not formatted properly. line {} \"{}\"", line_count, line)))
-        };
+impl Solution for Day4 {
+    type Input = Vec<(Range, Range)>;
 
-        if first_range.contains(&second_range) || second_range.contains(&first_range) {
-            total_contained_pairs += 1;
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        parse_reader(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(contained_pairs(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(overlapping_pairs(input).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// every range with bounds from 0 to 6
+    fn ranges() -> Vec<Range> {
+        let mut rtn = Vec::new();
+
+        for lower in 0..=6 {
+            for upper in lower..=6 {
+                rtn.push(Range { lower, upper });
+            }
         }
 
-        if first_range.overlaps(&second_range) || second_range.overlaps(&first_range) {
-            total_overlapping_pairs += 1;
+        rtn
+    }
+
+    fn sections(range: &Range) -> std::ops::RangeInclusive<u32> {
+        range.lower..=range.upper
+    }
+
+    #[test]
+    fn from_str() {
+        let range = Range::from_str("2-14").unwrap();
+
+        assert_eq!((range.lower, range.upper), (2, 14));
+        assert!(matches!(Range::from_str("2"), Err(FromStrError::InvalidFormat(_))));
+        assert!(matches!(Range::from_str("a-4"), Err(FromStrError::InvalidInteger(span)) if span == (0..1)));
+        assert!(matches!(Range::from_str("2-"), Err(FromStrError::InvalidInteger(span)) if span == (2..2)));
+        assert!(matches!(Range::from_str("5-4"), Err(FromStrError::InvalidLowerBound(_))));
+    }
+
+    #[test]
+    fn contains() {
+        for range in ranges() {
+            for check in ranges() {
+                let expected = sections(&check).all(|section| sections(&range).contains(&section));
+
+                assert_eq!(
+                    range.contains(&check),
+                    expected,
+                    "{}-{} contains {}-{}", range.lower, range.upper, check.lower, check.upper
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn overlaps() {
+        for range in ranges() {
+            for check in ranges() {
+                let expected = sections(&check).any(|section| sections(&range).contains(&section));
+
+                assert_eq!(
+                    range.overlaps(&check),
+                    expected,
+                    "{}-{} overlaps {}-{}", range.lower, range.upper, check.lower, check.upper
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn overlaps_is_symmetric() {
+        for range in ranges() {
+            for check in ranges() {
+                assert_eq!(range.overlaps(&check), check.overlaps(&range));
+            }
         }
     }
 
-    let finish = std::time::Instant::now();
+    #[test]
+    fn contains_implies_overlaps() {
+        for range in ranges() {
+            for check in ranges() {
+                if range.contains(&check) {
+                    assert!(range.overlaps(&check));
+                }
+            }
+        }
+    }
 
-    println!("total contained pairs: {}", total_contained_pairs);
-    println!("total overlapping pairs: {}", total_overlapping_pairs);
-    println!("total time: {:#?}", finish.duration_since(start));
+    #[test]
+    fn example() {
+        let answer = solve(EXAMPLE.input).unwrap();
 
-    Ok(())
-}
\ No newline at end of file
+        assert_eq!(answer.contained, 2);
+        assert_eq!(answer.overlapping, 4);
+    }
+}
@@ -1,94 +1,147 @@
-use std::env::Args;
+use std::io::BufRead;
 
 use crate::error;
-use crate::cli;
 use crate::io;
-
-struct Operation {
+use crate::day::{Solution, Part, Answer, Example};
+
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    ),
+    answers: ["CMZ", "MCD"],
+};
+
+pub struct Operation {
     amount: usize,
     from: usize,
     to: usize
 }
 
+/// why an operation could not be parsed. each variant holds the byte span of
+/// the problem in the parsed string
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 enum FromStrError {
-    InvalidInteger,
-    InvalidFormat
+    InvalidInteger(std::ops::Range<usize>),
+    InvalidFormat(std::ops::Range<usize>, &'static str),
+    InvalidStack(std::ops::Range<usize>, usize),
+}
+
+impl FromStrError {
+    fn into_diagnostic(self, line_number: usize, line: &str) -> error::Diagnostic {
+        match self {
+            FromStrError::InvalidInteger(span) => error::Diagnostic::integer(
+                line_number, line, span, "usize"
+            ),
+            FromStrError::InvalidFormat(span, expected) => error::Diagnostic::new(
+                line_number, line, span, format!("expected {}", expected)
+            ),
+            FromStrError::InvalidStack(span, stacks) => error::Diagnostic::new(
+                line_number, line, span, format!("expected a stack between 1 and {}", stacks)
+            ),
+        }
+    }
+}
+
+/// consumes the next token and checks that it is the given keyword. expected
+/// describes the keyword if it is not found
+fn expect_keyword(
+    chars: &mut std::str::CharIndices<'_>,
+    end: usize,
+    keyword: &str,
+    expected: &'static str
+) -> std::result::Result<(), FromStrError> {
+    match next_token(chars) {
+        Some((_, token)) if token == keyword => Ok(()),
+        Some((start, token)) => Err(FromStrError::InvalidFormat(start..(start + token.len()), expected)),
+        None => Err(FromStrError::InvalidFormat(end..end, expected)),
+    }
+}
+
+/// consumes the next token as an integer. expected describes the value if
+/// there are no more tokens
+fn expect_integer(
+    chars: &mut std::str::CharIndices<'_>,
+    end: usize,
+    expected: &'static str
+) -> std::result::Result<(usize, std::ops::Range<usize>), FromStrError> {
+    let Some((start, token)) = next_token(chars) else {
+        return Err(FromStrError::InvalidFormat(end..end, expected));
+    };
+
+    let span = start..(start + token.len());
+
+    let Ok(value) = token.parse::<usize>() else {
+        return Err(FromStrError::InvalidInteger(span));
+    };
+
+    Ok((value, span))
 }
 
 impl Operation {
-    fn from_str<S>(string: S) -> std::result::Result<Self, FromStrError>
+    /// parses a `move {amount} from {stack} to {stack}` line. stacks is the
+    /// number of stacks that the operation can refer to
+    fn from_str<S>(string: S, stacks: usize) -> std::result::Result<Self, FromStrError>
     where
         S: AsRef<str>
     {
         let str_ref = string.as_ref();
+        let end = str_ref.len();
+        let mut chars = str_ref.char_indices();
 
-        let mut splits = str_ref.split(' ');
-        // ignore the command
-        splits.next();
-        // amount to move
-        let amount = {
-            let Some(amount_str) = splits.next() else {
-                return Err(FromStrError::InvalidFormat);
-            };
-    
-            let Ok(amount) = usize::from_str_radix(amount_str, 10) else {
-                return Err(FromStrError::InvalidInteger);
-            };
+        expect_keyword(&mut chars, end, "move", "\"move\"")?;
 
-            amount
-        };
-
-        // from column
-        let from = {
-            let Some(_dest_str) = splits.next() else {
-                return Err(FromStrError::InvalidFormat);
-            };
-
-            let Some(column_str) = splits.next() else {
-                return Err(FromStrError::InvalidFormat);
-            };
+        let (amount, _) = expect_integer(&mut chars, end, "the amount of crates to move")?;
 
-            let Ok(column) = usize::from_str_radix(column_str, 10) else {
-                return Err(FromStrError::InvalidInteger);
-            };
+        expect_keyword(&mut chars, end, "from", "\"from\"")?;
 
-            column - 1
-        };
+        let (from, span) = expect_integer(&mut chars, end, "the stack to move from")?;
 
-        // to column
-        let to = {
-            let Some(_dest_str) = splits.next() else {
-                return Err(FromStrError::InvalidFormat);
-            };
+        if from == 0 || from > stacks {
+            return Err(FromStrError::InvalidStack(span, stacks));
+        }
 
-            let Some(column_str) = splits.next() else {
-                return Err(FromStrError::InvalidFormat);
-            };
+        expect_keyword(&mut chars, end, "to", "\"to\"")?;
 
-            let Ok(column) = usize::from_str_radix(column_str, 10) else {
-                return Err(FromStrError::InvalidInteger);
-            };
+        let (to, span) = expect_integer(&mut chars, end, "the stack to move to")?;
 
-            column - 1
-        };
+        if to == 0 || to > stacks {
+            return Err(FromStrError::InvalidStack(span, stacks));
+        }
 
-        Ok(Operation { amount, from, to })
+        Ok(Operation { amount, from: from - 1, to: to - 1 })
     }
 }
 
-fn next_token(iter: &mut impl Iterator<Item = char>) -> Option<String> {
+/// gets the next whitespace separated token along with the byte index that
+/// it starts at
+fn next_token(iter: &mut impl Iterator<Item = (usize, char)>) -> Option<(usize, String)> {
+    let mut start: usize = 0;
     let mut collected = String::new();
 
-    while let Some(ch) = iter.next() {
+    for (index, ch) in iter.by_ref() {
         if ch.is_ascii_whitespace() {
-            if collected.len() > 0 {
+            if !collected.is_empty() {
                 collected.shrink_to_fit();
 
-                return Some(collected);
+                return Some((start, collected));
             }
 
             continue;
         } else {
+            if collected.is_empty() {
+                start = index;
+            }
+
             if collected.len() + 1 == collected.capacity() {
                 collected.reserve(10);
             }
@@ -97,46 +150,60 @@ fn next_token(iter: &mut impl Iterator<Item = char>) -> Option<String> {
         }
     }
 
-    if collected.len() > 0 {
+    if !collected.is_empty() {
         collected.shrink_to_fit();
 
-        Some(collected)
+        Some((start, collected))
     } else {
         None
     }
 }
 
-pub fn run(mut args: Args) -> error::Result<()> {
-    let mut file_path: Option<String> = None;
+/// the line that numbers the stacks. used to point at a stack in errors
+pub struct StackLabels {
+    line_number: usize,
+    line: String,
+    /// the span of each stack number in the line
+    spans: Vec<std::ops::Range<usize>>,
+}
+
+pub struct Cargo {
+    columns: Vec<Vec<char>>,
+    labels: StackLabels,
+    operations: Vec<(usize, Operation)>,
+}
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
+/// gets the top crate of each column
+fn top_crates(columns: &[Vec<char>], labels: &StackLabels) -> error::Result<String> {
+    let mut output = String::new();
+
+    for (index, column) in columns.iter().enumerate() {
+        let Some(ch) = column.last() else {
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                labels.line_number,
+                labels.line.as_str(),
+                labels.spans[index].clone(),
+                "expected a crate on the stack after the last move"
+            )));
         };
 
-        match arg.as_str() {
-            "-f" | "--file" => {
-                file_path = Some(cli::get_arg_value(&mut args, "file")?);
-            },
-            _ => {
-                return Err(error::build::invalid_argument(arg));
-            }
-        }
+        output.push(*ch);
     }
 
-    let reader = cli::get_file_reader(file_path)?;
-    let mut line_reader = io::LineReader::new(reader);
+    Ok(output)
+}
 
-    let mut cargo_lines: Vec<String> = Vec::new();
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<Cargo> {
+    parse(&io::read_all(reader)?)
+}
 
-    let start = std::time::Instant::now();
+pub fn parse(input: &str) -> error::Result<Cargo> {
+    let mut lines = io::StrLines::new(input).normalize(io::Normalize::TRIM);
 
-    loop {
-        let Some(line) = line_reader.next_line()? else {
-            break;
-        };
+    let mut cargo_lines: Vec<&str> = Vec::new();
 
-        if line.len() == 0 {
+    for (_, line) in lines.by_ref() {
+        if line.is_empty() {
             break;
         }
 
@@ -144,64 +211,66 @@ pub fn run(mut args: Args) -> error::Result<()> {
     }
 
     // get the last line to determine how many columns there are
-    let Some(column_str) = cargo_lines.last() else {
+    let Some((&column_str, crate_lines)) = cargo_lines.split_last() else {
         return Err(error::Error::new(error::ErrorKind::BadInput)
             .with_message("no cargo lines specified"));
     };
 
-    let mut chars = column_str.chars();
+    let line_number = cargo_lines.len();
+    let mut chars = column_str.char_indices();
     let mut count: usize = 0;
     let mut prev_digit: u32 = 0;
+    let mut spans = Vec::new();
 
-    while let Some(token) = next_token(&mut chars) {
-        if cfg!(debug_assertions) {
-            println!("token: \"{}\"", token);
-        }
+    while let Some((start, token)) = next_token(&mut chars) {
+        crate::trace!(Trace, "day5", "token: \"{}\"", token);
+
+        let span = start..(start + token.len());
 
-        let Ok(num) = u32::from_str_radix(&token, 10) else {
-            return Err(error::build::bad_line_input(cargo_lines.len(), column_str))
+        let Ok(num) = token.parse::<u32>() else {
+            return Err(error::build::bad_input(
+                error::Diagnostic::integer(line_number, column_str, span, "u32")
+            ));
         };
 
         if num <= prev_digit {
-            return Err(error::build::bad_line_input(cargo_lines.len(), column_str));
+            return Err(error::build::bad_input(error::Diagnostic::new(
+                line_number,
+                column_str,
+                span,
+                format!("expected a stack number greater than {}", prev_digit)
+            )));
         }
 
         count += 1;
         prev_digit = num;
+        spans.push(span);
     }
 
     let mut running: usize = 0;
-    let mut p1_columns: Vec<Vec<char>> = Vec::with_capacity(count);
-
-    for _ in 0..count {
-        p1_columns.push(Vec::new())
-    }
+    let mut columns: Vec<Vec<char>> = vec![Vec::new(); count];
 
-    for index in (0..(cargo_lines.len() - 1)).rev() {
-        let mut chars = cargo_lines[index].chars();
+    for line in crate_lines.iter().rev() {
+        let mut chars = line.chars();
         chars.next();
-        
-        if cfg!(debug_assertions) {
-            println!("cargo line: \"{}\"", cargo_lines[index]);
-        }
+
+        crate::trace!(Trace, "day5", "cargo line: \"{}\"", line);
 
         'outer: while running < count {
             let Some(ch) = chars.next() else {
                 break;
             };
 
-            if cfg!(debug_assertions) {
-                println!("found crate: \"{}\"", ch);
-            }
+            crate::trace!(Trace, "day5", "found crate: \"{}\"", ch);
 
             if ch.is_alphabetic() {
-                p1_columns[running].push(ch);
+                columns[running].push(ch);
             }
 
             running += 1;
 
             for _ in 0..3 {
-                if let None = chars.next() {
+                if chars.next().is_none() {
                     break 'outer;
                 }
             }
@@ -210,98 +279,267 @@ pub fn run(mut args: Args) -> error::Result<()> {
         running = 0;
     }
 
-    let mut p2_columns = p1_columns.clone();
-    let mut intermediate: Vec<char> = Vec::new();
+    let mut operations = Vec::new();
 
-    loop {
-        let Some(line) = line_reader.next_line()? else {
-            break;
+    for (line_count, line) in lines {
+        let op = match Operation::from_str(line, columns.len()) {
+            Ok(op) => op,
+            Err(err) => {
+                return Err(error::build::bad_input(err.into_diagnostic(line_count, line)));
+            }
         };
 
-        let Ok(op) = Operation::from_str(&line) else {
-            return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
-        };
+        operations.push((line_count, op));
+    }
+
+    let labels = StackLabels {
+        line_number,
+        line: column_str.to_owned(),
+        spans,
+    };
+
+    Ok(Cargo { columns, labels, operations })
+}
+
+fn not_enough_crates(line_count: usize) -> error::Error {
+    error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("not enough crates to move. line {}", line_count))
+}
+
+/// moves the crates one at a time and gets the top crate of each column
+pub fn crate_mover_9000(input: &Cargo) -> error::Result<String> {
+    let mut columns = input.columns.clone();
+
+    for (line_count, op) in &input.operations {
+        if columns[op.from].len() < op.amount {
+            return Err(not_enough_crates(*line_count));
+        }
+
+        // check array sizes and make sure they have capacity
+        let avail = columns[op.to].capacity() - columns[op.to].len();
+
+        if avail < op.amount {
+            columns[op.to].reserve(op.amount - avail);
+        }
 
-        {
-            if op.from >= p1_columns.len() || op.to >= p1_columns.len() {
-                return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
+        for _ in 0..op.amount {
+            let Some(m) = columns[op.from].pop() else {
+                return Err(not_enough_crates(*line_count));
             };
 
-            // check array sizes and make sure they have capacity
-            let avail = p1_columns[op.to].capacity() - p1_columns[op.to].len();
+            columns[op.to].push(m);
+        }
+    }
 
-            if avail < op.amount {
-                p1_columns[op.to].reserve(op.amount - avail);
-            }
+    top_crates(&columns, &input.labels)
+}
 
-            for _ in 0..op.amount {
-                let Some(m) = p1_columns[op.from].pop() else {
-                    return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
-                };
+/// moves the crates all at once and gets the top crate of each column
+pub fn crate_mover_9001(input: &Cargo) -> error::Result<String> {
+    let mut columns = input.columns.clone();
+    let mut intermediate: Vec<char> = Vec::new();
 
-                p1_columns[op.to].push(m);
-            }
+    for (line_count, op) in &input.operations {
+        if columns[op.from].len() < op.amount {
+            return Err(not_enough_crates(*line_count));
+        }
+
+        // check array sizes and make sure they have capacity
+        let avail = columns[op.to].capacity() - columns[op.to].len();
+
+        if avail < op.amount {
+            columns[op.to].reserve(op.amount - avail);
         }
 
-        {
-            // this is probably pointless
-            if op.from >= p2_columns.len() || op.to >= p2_columns.len() {
-                return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
+        if intermediate.capacity() < op.amount {
+            intermediate.reserve(op.amount - intermediate.capacity());
+        }
+
+        for _ in 0..op.amount {
+            let Some(m) = columns[op.from].pop() else {
+                return Err(not_enough_crates(*line_count));
             };
 
-            // check array sizes and make sure they have capacity
-            let avail = p2_columns[op.to].capacity() - p2_columns[op.to].len();
+            intermediate.push(m);
+        }
 
-            if avail < op.amount {
-                p2_columns[op.to].reserve(op.amount - avail);
-            }
+        while let Some(m) = intermediate.pop() {
+            columns[op.to].push(m);
+        }
+    }
 
-            if intermediate.capacity() < op.amount {
-                intermediate.reserve(op.amount - intermediate.capacity());
-            }
+    top_crates(&columns, &input.labels)
+}
 
-            for _ in 0..op.amount {
-                let Some(m) = p2_columns[op.from].pop() else {
-                    return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
-                };
+/// solves both parts, returning the top crates for part 1 and part 2
+pub fn solve(input: &str) -> error::Result<(String, String)> {
+    let cargo = parse(input)?;
 
-                intermediate.push(m);
+    Ok((crate_mover_9000(&cargo)?, crate_mover_9001(&cargo)?))
+}
+
+#[derive(Default)]
+pub struct Day5 {}
+
+impl Solution for Day5 {
+    type Input = Cargo;
+
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        parse_reader(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(crate_mover_9000(input)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(crate_mover_9001(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(string: &str) -> Vec<(usize, String)> {
+        let mut chars = string.char_indices();
+        let mut rtn = Vec::new();
+
+        while let Some(token) = next_token(&mut chars) {
+            rtn.push(token);
+        }
+
+        rtn
+    }
+
+    #[test]
+    fn tokens_with_start() {
+        assert_eq!(tokens(""), vec![]);
+        assert_eq!(tokens("   "), vec![]);
+        assert_eq!(tokens("move"), vec![(0, String::from("move"))]);
+        assert_eq!(
+            tokens("  move 12\tfrom  "),
+            vec![(2, String::from("move")), (7, String::from("12")), (10, String::from("from"))]
+        );
+        assert_eq!(tokens("é ü"), vec![(0, String::from("é")), (3, String::from("ü"))]);
+    }
+
+    #[test]
+    fn next_token_matches_split_whitespace() {
+        // every string of up to 6 characters from a small alphabet
+        let alphabet = ['a', 'b', ' ', '\t', 'é'];
+        let mut strings = vec![String::new()];
+
+        for _ in 0..6 {
+            let mut next = Vec::with_capacity(strings.len() * alphabet.len());
+
+            for string in &strings {
+                for ch in alphabet {
+                    let mut added = string.clone();
+                    added.push(ch);
+                    next.push(added);
+                }
             }
 
-            for _ in 0..intermediate.len() {
-                let m = intermediate.pop().unwrap();
+            for string in &next {
+                let found = tokens(string);
+                let expected: Vec<&str> = string.split_ascii_whitespace().collect();
 
-                p2_columns[op.to].push(m);
+                assert_eq!(found.len(), expected.len(), "{:?}", string);
+
+                for ((start, token), expected) in found.iter().zip(expected) {
+                    assert_eq!(token, expected, "{:?}", string);
+                    assert_eq!(&string[*start..(*start + token.len())], expected, "{:?}", string);
+                }
             }
+
+            strings = next;
         }
     }
 
-    let finish = std::time::Instant::now();
+    #[test]
+    fn operation_from_str() {
+        let op = Operation::from_str("move 3 from 1 to 9", 9).unwrap();
+
+        assert_eq!((op.amount, op.from, op.to), (3, 0, 8));
+    }
+
+    #[test]
+    fn operation_from_str_errors() {
+        let stack_zero = Operation::from_str("move 1 from 0 to 2", 3);
+        assert!(matches!(stack_zero, Err(FromStrError::InvalidStack(span, 3)) if span == (12..13)));
 
-    let mut p1_output = String::new();
-    let mut p2_output = String::new();
+        let stack_over = Operation::from_str("move 1 from 2 to 4", 3);
+        assert!(matches!(stack_over, Err(FromStrError::InvalidStack(span, 3)) if span == (17..18)));
 
-    for index in 0..p1_columns.len() {
-        let Some(ch) = p1_columns[index].last() else {
-            return Err(error::Error::new(error::ErrorKind::Unexpected)
-                .with_message("a column has no value"));
-        };
+        let keyword = Operation::from_str("move 1 fro 2 to 3", 3);
+        assert!(matches!(keyword, Err(FromStrError::InvalidFormat(span, "\"from\"")) if span == (7..10)));
+
+        let integer = Operation::from_str("move one from 2 to 3", 3);
+        assert!(matches!(integer, Err(FromStrError::InvalidInteger(span)) if span == (5..8)));
 
-        p1_output.push(ch.clone());
+        let missing = Operation::from_str("move 1 from 2", 3);
+        assert!(matches!(missing, Err(FromStrError::InvalidFormat(span, "\"to\"")) if span == (13..13)));
+
+        assert!(Operation::from_str("", 3).is_err());
     }
 
-    for index in 0..p2_columns.len() {
-        let Some(ch) = p2_columns[index].last() else {
-            return Err(error::Error::new(error::ErrorKind::Unexpected)
-                .with_message("a column has no value"));
-        };
+    #[test]
+    fn operation_from_str_checks_every_stack() {
+        for stacks in 1..=4 {
+            for from in 0..=5 {
+                for to in 0..=5 {
+                    let line = format!("move 1 from {} to {}", from, to);
+                    let valid = (1..=stacks).contains(&from) && (1..=stacks).contains(&to);
+
+                    match Operation::from_str(&line, stacks) {
+                        Ok(op) => {
+                            assert!(valid, "{:?} with {} stacks", line, stacks);
+                            assert_eq!((op.from, op.to), (from - 1, to - 1));
+                        },
+                        Err(err) => {
+                            assert!(!valid, "{:?} with {} stacks", line, stacks);
+                            assert!(matches!(err, FromStrError::InvalidStack(_, count) if count == stacks));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn move_more_than_stack() {
+        let input = "[A]\n 1 \n\nmove 99999999999 from 1 to 1\n";
+
+        assert!(crate_mover_9000(&parse(input).unwrap()).is_err());
+        assert!(crate_mover_9001(&parse(input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn empty_stack() {
+        let input = "[A]    \n 1   2 \n\nmove 1 from 1 to 2\n";
+        let cargo = parse(input).unwrap();
+
+        for result in [crate_mover_9000(&cargo), crate_mover_9001(&cargo)] {
+            let err = result.unwrap_err();
+
+            assert_eq!(err.kind, error::ErrorKind::BadInput);
 
-        p2_output.push(ch.clone());
+            let diagnostic = err.diagnostic.unwrap();
+
+            assert_eq!((diagnostic.line_number, diagnostic.span), (2, 1..2));
+        }
     }
 
-    println!("p1 end sequence: \"{}\"", p1_output);
-    println!("p2 end sequence: \"{}\"", p2_output);
-    println!("total duration: {:#?}", finish.duration_since(start));
+    #[test]
+    fn example() {
+        let (first, second) = solve(EXAMPLE.input).unwrap();
 
-    Ok(())
-}
\ No newline at end of file
+        assert_eq!(first, "CMZ");
+        assert_eq!(second, "MCD");
+    }
+}
@@ -1,10 +1,16 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::env::Args;
+use std::io::BufRead;
 
 use crate::error;
-use crate::cli;
 use crate::io;
+use crate::day::{Solution, Part, Answer, Example};
+
+/// the example from the puzzle text
+pub const EXAMPLE: Example = Example {
+    input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n",
+    answers: ["7", "19"],
+};
 
 struct UniqueSequence {
     total: usize,
@@ -34,7 +40,7 @@ impl UniqueSequence {
         self.repeating != 0
     }
 
-    fn remove_front(&mut self) -> () {
+    fn remove_front(&mut self) {
         let dropped = self.seq.pop_front().unwrap();
 
         let remove = {
@@ -56,7 +62,7 @@ impl UniqueSequence {
         }
     }
 
-    fn add_char(&mut self, ch: char) -> () {
+    fn add_char(&mut self, ch: char) {
         if self.seq.len() == self.total {
             self.remove_front();
         }
@@ -68,111 +74,186 @@ impl UniqueSequence {
 
             *count += 1;
         } else {
-            self.known.insert(ch.clone(), 1);
+            self.known.insert(ch, 1);
         }
 
         self.seq.push_back(ch);
     }
 }
 
-pub fn run(mut args: Args) -> error::Result<()> {
-    let mut file_path: Option<String> = None;
+/// finds the index after the first sequence of unique characters with the
+/// given length
+fn find_marker(signal_data: &str, total: usize, name: &str) -> error::Result<usize> {
+    let mut seq = UniqueSequence::new(total);
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
-        };
+    for (index, ch) in signal_data.chars().enumerate() {
+        seq.add_char(ch);
 
-        match arg.as_str() {
-            "-f" | "--file" => {
-                file_path = Some(cli::get_arg_value(&mut args, "file")?);
-            },
-            _ => {
-                return Err(error::build::invalid_argument(arg));
-            }
+        crate::trace!(Trace, "day6", "{} seq: {:?}", name, seq.seq);
+
+        if seq.is_filled() && !seq.has_repeating() {
+            crate::trace!(Debug, "day6", "start of {} found at {}", name, index + 1);
+
+            return Ok(index + 1);
         }
     }
 
-    let reader = cli::get_file_reader(file_path)?;
-    let mut line_reader = io::LineReader::new(reader);
-    let mut signal_data = String::new();
+    Err(error::Error::new(error::ErrorKind::BadInput)
+        .with_message(format!("no start of {} found", name)))
+}
 
-    let start = std::time::Instant::now();
+/// the answers for both parts of day 6
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day6Answer {
+    /// the number of characters processed before the start of packet marker
+    pub packet_start: usize,
+    /// the number of characters processed before the start of message marker
+    pub message_start: usize,
+}
 
-    loop {
-        let Some(line) = line_reader.next_line()? else {
-            break;
-        };
+pub fn parse_reader(reader: &mut dyn BufRead) -> error::Result<String> {
+    parse(&io::read_all(reader)?)
+}
 
-        if line.len() == 0 {
-            return Err(error::build::bad_line_input(line_reader.get_count().clone(), line));
-        }
+pub fn parse(input: &str) -> error::Result<String> {
+    let Some((line_count, line)) = io::StrLines::new(input).normalize(io::Normalize::TRIM).next() else {
+        return Err(error::Error::new(error::ErrorKind::BadInput)
+            .with_message("no signal data provided"));
+    };
 
-        signal_data = line;
-        break;
+    if line.is_empty() {
+        return Err(error::build::bad_input(error::Diagnostic::new(
+            line_count, line, 0..0, "expected signal data"
+        )));
     }
 
-    let mut index: usize = 0;
-    let mut start_of_packet: usize = 0;
-    let mut start_of_message: usize = 0;
-    let mut completed: u32 = 0;
-    let mut chars = signal_data.chars();
-    let mut packet_seq = UniqueSequence::new(4);
-    let mut message_seq = UniqueSequence::new(14);
-
-    loop {
-        let Some(ch) = chars.next() else {
-            break;
-        };
+    Ok(line.to_owned())
+}
 
-        index += 1;
+/// the number of characters processed before the start of packet marker
+pub fn packet_start(signal_data: &str) -> error::Result<usize> {
+    find_marker(signal_data, 4, "packet")
+}
 
-        if start_of_packet == 0 {
-            packet_seq.add_char(ch.clone());
+/// the number of characters processed before the start of message marker
+pub fn message_start(signal_data: &str) -> error::Result<usize> {
+    find_marker(signal_data, 14, "message")
+}
 
-            if cfg!(debug_assertions) {
-                println!("packet seq: {:?}", packet_seq.seq);
-            }
+pub fn solve(input: &str) -> error::Result<Day6Answer> {
+    let signal_data = parse(input)?;
 
-            if packet_seq.is_filled() {
-                if !packet_seq.has_repeating() {
-                    start_of_packet = index;
+    Ok(Day6Answer {
+        packet_start: packet_start(&signal_data)?,
+        message_start: message_start(&signal_data)?,
+    })
+}
 
-                    completed += 1;
+#[derive(Default)]
+pub struct Day6 {}
 
-                    if completed == 2 {
-                        break;
-                    }
+impl Solution for Day6 {
+    type Input = String;
+
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    const EXAMPLE: Example = EXAMPLE;
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input> {
+        parse_reader(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(packet_start(input)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> error::Result<Answer> {
+        Ok(message_start(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unique_sequence() {
+        let mut seq = UniqueSequence::new(3);
+
+        seq.add_char('a');
+        seq.add_char('b');
+        assert!(!seq.is_filled());
+
+        seq.add_char('a');
+        assert!(seq.is_filled());
+        assert!(seq.has_repeating());
+
+        seq.add_char('c');
+        assert!(!seq.has_repeating());
+
+        seq.add_char('c');
+        assert!(seq.has_repeating());
+    }
+
+    #[test]
+    fn unique_sequence_matches_window() {
+        // every string of up to 7 characters from a small alphabet
+        let alphabet = ['a', 'b', 'c', 'd'];
+        let mut strings = vec![String::new()];
+
+        for _ in 0..7 {
+            let mut next = Vec::with_capacity(strings.len() * alphabet.len());
+
+            for string in &strings {
+                for ch in alphabet {
+                    let mut added = string.clone();
+                    added.push(ch);
+                    next.push(added);
                 }
             }
-        }
 
-        if start_of_message == 0 {
-            message_seq.add_char(ch.clone());
+            strings = next;
 
-            if cfg!(debug_assertions) {
-                println!("message seq: {:?}", message_seq.seq);
-            }
+            for string in &strings {
+                let chars: Vec<char> = string.chars().collect();
 
-            if message_seq.is_filled() {
-                if !message_seq.has_repeating() {
-                    start_of_message = index;
+                for total in 1..=4 {
+                    let mut seq = UniqueSequence::new(total);
 
-                    completed += 1;
+                    for end in 1..=chars.len() {
+                        seq.add_char(chars[end - 1]);
+
+                        let window = &chars[end.saturating_sub(total)..end];
+                        let repeating = window.iter()
+                            .enumerate()
+                            .any(|(index, ch)| window[(index + 1)..].contains(ch));
+
+                        assert_eq!(seq.is_filled(), end >= total, "{:?} {}", &chars[..end], total);
+                        assert_eq!(seq.has_repeating(), repeating, "{:?} {}", &chars[..end], total);
+                    }
                 }
             }
         }
+    }
 
-        if completed == 2 {
-            break;
+    #[test]
+    fn markers() {
+        let cases = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+
+        for (signal, packet, message) in cases {
+            assert_eq!(packet_start(signal).unwrap(), packet, "{}", signal);
+            assert_eq!(message_start(signal).unwrap(), message, "{}", signal);
         }
     }
 
-    let finish = std::time::Instant::now();
-
-    println!("packet start: {} {:?}", start_of_packet, packet_seq.seq);
-    println!("message start: {} {:?}", start_of_message, message_seq.seq);
-    println!("total duration: {:#?}", finish.duration_since(start));
-
-    Ok(())
-}
\ No newline at end of file
+    #[test]
+    fn no_marker() {
+        assert!(packet_start("aabbaabb").is_err());
+    }
+}
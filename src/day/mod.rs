@@ -1,6 +1,320 @@
+use std::io::BufRead;
+use std::time::Duration;
+
+use crate::error;
+use crate::cli;
+use crate::output::Format;
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
 pub mod day4;
 pub mod day5;
-pub mod day6;
\ No newline at end of file
+pub mod day6;
+
+/// the parts that make up a days puzzle
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn try_from_str<S>(parse: S) -> Option<Part>
+    where
+        S: AsRef<str>
+    {
+        match parse.as_ref() {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// the value produced by solving a part of a day
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(v) => write!(f, "{}", v),
+            Answer::Text(v) => f.write_str(v),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::Number(v as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::Number(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::Number(v as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+/// the small worked example from the puzzle text and its known answers
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    pub input: &'static str,
+    pub answers: [&'static str; 2],
+}
+
+impl Example {
+    /// the expected answer for the part
+    pub fn answer(&self, part: Part) -> &'static str {
+        match part {
+            Part::One => self.answers[0],
+            Part::Two => self.answers[1],
+        }
+    }
+}
+
+/// a solver for a single day
+///
+/// the implementing struct holds any day specific options so it should be
+/// configured with [`Solution::configure`] before anything is parsed.
+pub trait Solution {
+    /// the parsed representation of the puzzle input
+    type Input;
+
+    /// the parts that are implemented for this day
+    const PARTS: &'static [Part];
+
+    /// the example from the puzzle text
+    const EXAMPLE: Example;
+
+    /// the options that are specific to the day
+    const OPTIONS: &'static [cli::Opt] = &[];
+
+    /// applies the values of the day specific options
+    fn configure(&mut self, _matches: &cli::Matches) -> error::Result<()> {
+        Ok(())
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> error::Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> error::Result<Answer>;
+
+    fn part2(&self, _input: &Self::Input) -> error::Result<Answer> {
+        Err(error::build::part_not_implemented(Part::Two))
+    }
+
+    /// extra output about the input for options that ask for it. printed
+    /// after the answers
+    fn report(&self, _input: &Self::Input) -> error::Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// the format of the report. a report that is not text has to be
+    /// written to a file so that it is not mixed with the answers
+    fn report_format(&self) -> Format {
+        Format::Text
+    }
+
+    /// solves the requested part
+    fn solve(&self, part: Part, input: &Self::Input) -> error::Result<Answer> {
+        if !Self::PARTS.contains(&part) {
+            return Err(error::build::part_not_implemented(part));
+        }
+
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+/// the result of a single part
+pub struct PartOutcome {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// the results from running a day
+pub struct Outcome {
+    pub parse_time: Duration,
+    pub parts: Vec<PartOutcome>,
+    /// the output of [`Solution::report`]
+    pub report: Option<String>,
+}
+
+/// the time each phase took for every recorded run of a benchmark
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub parts: Vec<(Part, Vec<Duration>)>,
+}
+
+/// type erased version of [`Solution`] so that days can be stored in the
+/// registry
+pub trait Runner {
+    fn options(&self) -> &'static [cli::Opt];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()>;
+
+    fn parts(&self) -> &'static [Part];
+
+    fn example(&self) -> Example;
+
+    fn report_format(&self) -> Format;
+
+    fn run(&self, reader: &mut dyn BufRead, parts: &[Part]) -> error::Result<Outcome>;
+
+    /// parses and solves the input repeatedly, recording the time of each
+    /// phase. the warm up runs are not recorded
+    fn bench(&self, input: &[u8], parts: &[Part], warmup: usize, iterations: usize) -> error::Result<Samples>;
+}
+
+impl<S: Solution> Runner for S {
+    fn options(&self) -> &'static [cli::Opt] {
+        S::OPTIONS
+    }
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        Solution::configure(self, matches)
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn example(&self) -> Example {
+        S::EXAMPLE
+    }
+
+    fn report_format(&self) -> Format {
+        Solution::report_format(self)
+    }
+
+    fn run(&self, reader: &mut dyn BufRead, parts: &[Part]) -> error::Result<Outcome> {
+        // every puzzle has at least one line so an empty input is most
+        // likely the wrong file or nothing piped in
+        if reader.fill_buf()?.is_empty() {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message("the input is empty"));
+        }
+
+        let start = std::time::Instant::now();
+        let input = self.parse(reader)?;
+        let parse_time = start.elapsed();
+
+        let mut outcomes = Vec::with_capacity(parts.len());
+
+        for part in parts {
+            let start = std::time::Instant::now();
+            let answer = self.solve(*part, &input)?;
+            let time = start.elapsed();
+
+            outcomes.push(PartOutcome {
+                part: *part,
+                answer,
+                time
+            });
+        }
+
+        Ok(Outcome {
+            parse_time,
+            parts: outcomes,
+            report: self.report(&input)?,
+        })
+    }
+
+    fn bench(&self, input: &[u8], parts: &[Part], warmup: usize, iterations: usize) -> error::Result<Samples> {
+        let mut samples = Samples {
+            parse: Vec::with_capacity(iterations),
+            parts: parts.iter()
+                .map(|part| (*part, Vec::with_capacity(iterations)))
+                .collect()
+        };
+
+        for run in 0..(warmup + iterations) {
+            let recording = run >= warmup;
+
+            let start = std::time::Instant::now();
+            let parsed = self.parse(&mut &input[..])?;
+            let parse_time = start.elapsed();
+
+            if recording {
+                samples.parse.push(parse_time);
+            }
+
+            for (part, times) in samples.parts.iter_mut() {
+                let start = std::time::Instant::now();
+                std::hint::black_box(self.solve(*part, &parsed)?);
+                let time = start.elapsed();
+
+                if recording {
+                    times.push(time);
+                }
+            }
+        }
+
+        Ok(samples)
+    }
+}
+
+/// a registered day
+pub struct Entry {
+    pub day: u8,
+    pub title: &'static str,
+    build: fn() -> Box<dyn Runner>,
+}
+
+impl Entry {
+    /// creates a new runner with the default options for the day
+    pub fn build(&self) -> Box<dyn Runner> {
+        (self.build)()
+    }
+}
+
+fn build<S>() -> Box<dyn Runner>
+where
+    S: Solution + Default + 'static
+{
+    Box::new(S::default())
+}
+
+/// every day that is available to run. adding a new day only requires a new
+/// module and an entry here
+pub static REGISTRY: &[Entry] = &[
+    Entry { day: 1, title: "Calorie Counting", build: build::<day1::Day1> },
+    Entry { day: 2, title: "Rock Paper Scissors", build: build::<day2::Day2> },
+    Entry { day: 3, title: "Rucksack Reorganization", build: build::<day3::Day3> },
+    Entry { day: 4, title: "Camp Cleanup", build: build::<day4::Day4> },
+    Entry { day: 5, title: "Supply Stacks", build: build::<day5::Day5> },
+    Entry { day: 6, title: "Tuning Trouble", build: build::<day6::Day6> },
+];
+
+/// finds the registered day
+pub fn get(day: u8) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
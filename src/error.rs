@@ -1,7 +1,9 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorKind {
     InvalidArgument,
     MissingArgument,
     BadInput,
+    CheckFailed,
     Unexpected,
     IoError,
 }
@@ -12,18 +14,143 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::InvalidArgument => f.write_str("InvalidArgument"),
             ErrorKind::MissingArgument => f.write_str("MissingArgument"),
             ErrorKind::BadInput => f.write_str("BadInput"),
+            ErrorKind::CheckFailed => f.write_str("CheckFailed"),
             ErrorKind::Unexpected => f.write_str("Unexpected"),
             ErrorKind::IoError => f.write_str("IoError"),
         }
     }
 }
 
+impl ErrorKind {
+    /// the exit code of the process when it fails with this kind of error.
+    /// every kind has its own code so that scripts can tell them apart
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            ErrorKind::InvalidArgument => 2,
+            ErrorKind::MissingArgument => 3,
+            ErrorKind::BadInput => 4,
+            ErrorKind::CheckFailed => 5,
+            ErrorKind::IoError => 6,
+            ErrorKind::Unexpected => 7,
+        }
+    }
+}
+
 type BoxDynError = Box<dyn std::error::Error + Send + Sync>;
 
+/// points to the part of a line in the input that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// the input the line came from, if known
+    pub path: Option<String>,
+    pub line_number: usize,
+    pub line: String,
+    /// the byte range in the line that has the problem
+    pub span: std::ops::Range<usize>,
+    /// what was expected at the span
+    pub reason: String,
+}
+
+impl Diagnostic {
+    pub fn new<L, R>(line_number: usize, line: L, span: std::ops::Range<usize>, reason: R) -> Self
+    where
+        L: Into<String>,
+        R: Into<String>
+    {
+        let line = line.into();
+        let mut end = span.end.min(line.len());
+        let mut start = span.start.min(end);
+
+        // keep the span on char boundaries so the line can be sliced with it
+        while !line.is_char_boundary(end) {
+            end += 1;
+        }
+
+        while !line.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        Diagnostic {
+            path: None,
+            line_number,
+            line,
+            span: start..end,
+            reason: reason.into(),
+        }
+    }
+
+    /// creates a diagnostic for text in a line that should be an integer of
+    /// the given type. points to the first character that is not a digit or
+    /// the whole text if the value is too large
+    pub fn integer<L>(line_number: usize, line: L, span: std::ops::Range<usize>, int_type: &str) -> Self
+    where
+        L: Into<String>
+    {
+        let line = line.into();
+        let text = line.get(span.clone()).unwrap_or("");
+
+        if text.is_empty() {
+            return Diagnostic::new(line_number, line, span.clone(), "expected an integer");
+        }
+
+        if let Some((index, ch)) = text.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+            let start = span.start + index;
+
+            return Diagnostic::new(line_number, line, start..(start + ch.len_utf8()), "expected a digit");
+        }
+
+        Diagnostic::new(line_number, line, span, format!("expected an integer that fits in a {}", int_type))
+    }
+
+    /// the column of the start of the span, counted in characters from 1
+    pub fn column(&self) -> usize {
+        self.line[..self.span.start].chars().count() + 1
+    }
+
+    /// renders the line with the span underlined
+    ///
+    /// ```text
+    ///  --> inputs/day4/test.txt:3:7
+    ///   |
+    /// 3 | 2-4,6-x
+    ///   |       ^ expected a digit
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line_number.to_string();
+        let gutter = " ".repeat(number.len());
+
+        let mut underline = String::new();
+
+        for ch in self.line[..self.span.start].chars() {
+            underline.push(if ch == '\t' { '\t' } else { ' ' });
+        }
+
+        let carets = self.line[self.span.clone()].chars().count().max(1);
+
+        for _ in 0..carets {
+            underline.push('^');
+        }
+
+        format!(
+            "{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {} {}",
+            self.path.as_deref().unwrap_or("input"),
+            self.line_number,
+            self.column(),
+            number,
+            self.line,
+            underline,
+            self.reason,
+            gutter = gutter,
+        )
+    }
+}
+
+#[derive(Debug)]
 pub struct Error {
     pub kind: ErrorKind,
     pub message: Option<String>,
-    pub source: Option<BoxDynError>
+    pub source: Option<BoxDynError>,
+    pub diagnostic: Option<Box<Diagnostic>>,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -34,6 +161,7 @@ impl Error {
             kind,
             message: None,
             source: None,
+            diagnostic: None,
         }
     }
 
@@ -52,6 +180,63 @@ impl Error {
         self.message = Some(message.into());
         self
     }
+
+    pub fn with_diagnostic(mut self, diagnostic: Diagnostic) -> Self {
+        self.diagnostic = Some(Box::new(diagnostic));
+        self
+    }
+
+    /// sets the input that the diagnostic points into, if there is one
+    pub fn in_input<P>(mut self, path: P) -> Self
+    where
+        P: Into<String>
+    {
+        if let Some(diagnostic) = self.diagnostic.as_mut() {
+            diagnostic.path = Some(path.into());
+        }
+
+        self
+    }
+
+    /// iterates through the sources of the error, starting with the one that
+    /// directly caused it
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(
+            std::error::Error::source(self),
+            |err| err.source()
+        )
+    }
+
+    /// single line version of the error for places that cannot print the
+    /// full error
+    pub fn summary(&self) -> String {
+        let mut rtn = self.to_string();
+
+        if let Some(source) = &self.source {
+            rtn.push_str(": ");
+            rtn.push_str(&source.to_string());
+        }
+
+        rtn
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|err| err.as_ref() as &(dyn std::error::Error + 'static))
+    }
 }
 
 macro_rules! generic_catch {
@@ -74,7 +259,8 @@ macro_rules! generic_catch {
 generic_catch!(std::io::Error, ErrorKind::IoError);
 
 pub mod build {
-    use super::{Error, ErrorKind};
+    use super::{Error, ErrorKind, Diagnostic};
+    use crate::day::Part;
 
     /// common error for providing an invalid argument
     #[inline]
@@ -87,22 +273,88 @@ pub mod build {
             .with_message(msg)
     }
 
+    /// an option was given a value that could not be used
     #[inline]
-    pub fn no_file_provided() -> Error {
-        Error::new(ErrorKind::MissingArgument)
-            .with_message("no file provided for input")
+    pub fn invalid_value<N, E, V>(name: N, expected: E, value: V) -> Error
+    where
+        N: AsRef<str>,
+        E: AsRef<str>,
+        V: AsRef<str>
+    {
+        Error::new(ErrorKind::InvalidArgument)
+            .with_message(format!(
+                "invalid value for --{}. expected {}. value: {}",
+                name.as_ref(),
+                expected.as_ref(),
+                value.as_ref()
+            ))
     }
 
+    /// an option that needs a value was not given one
     #[inline]
-    pub fn bad_line_input<S>(count: usize, line: S) -> Error
+    pub fn missing_value<N>(name: N) -> Error
     where
-        S: AsRef<str>
+        N: AsRef<str>
     {
+        Error::new(ErrorKind::MissingArgument)
+            .with_message(format!("missing --{} argument value", name.as_ref()))
+    }
+
+    #[inline]
+    pub fn part_not_implemented(part: Part) -> Error {
+        Error::new(ErrorKind::InvalidArgument)
+            .with_message(format!("part {} is not implemented", part))
+    }
+
+    #[inline]
+    pub fn check_failed(failures: usize) -> Error {
+        Error::new(ErrorKind::CheckFailed)
+            .with_message(format!("{} check(s) did not match the expected answers", failures))
+    }
+
+    #[inline]
+    pub fn regression_found(regressions: usize, threshold: f64) -> Error {
+        Error::new(ErrorKind::CheckFailed)
+            .with_message(format!("{} phase(s) regressed by more than {}%", regressions, threshold))
+    }
+
+    /// a line of the puzzle input could not be parsed
+    #[inline]
+    pub fn bad_input(diagnostic: Diagnostic) -> Error {
         Error::new(ErrorKind::BadInput)
             .with_message(format!(
-                "a line in the file could not be parsed. line: {} \"{}\"", 
-                count, 
-                line.as_ref()
+                "a line in the file could not be parsed. {} at line {} column {}",
+                diagnostic.reason,
+                diagnostic.line_number,
+                diagnostic.column()
             ))
+            .with_diagnostic(diagnostic)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diagnostic_spans() {
+        let diagnostic = Diagnostic::integer(3, "2-4,6-x", 6..7, "u32");
+
+        assert_eq!(diagnostic.span, 6..7);
+        assert_eq!(diagnostic.column(), 7);
+        assert_eq!(diagnostic.reason, "expected a digit");
+        assert!(diagnostic.render().ends_with("3 | 2-4,6-x\n  |       ^ expected a digit"), "{}", diagnostic.render());
+
+        let diagnostic = Diagnostic::integer(1, "99999999999", 0..11, "u32");
+
+        assert_eq!(diagnostic.span, 0..11);
+        assert_eq!(diagnostic.reason, "expected an integer that fits in a u32");
+
+        // spans are clamped to the line and widened to char boundaries
+        let diagnostic = Diagnostic::new(1, "aé b", 2..3, "reason");
+
+        assert_eq!(diagnostic.span, 1..3);
+        assert_eq!(diagnostic.column(), 2);
+        assert_eq!(Diagnostic::new(1, "ab", 5..9, "reason").span, 2..2);
     }
-}
\ No newline at end of file
+}
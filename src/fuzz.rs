@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+use std::panic::{self, AssertUnwindSafe};
+use std::cell::{Cell, RefCell};
+use std::sync::Once;
+
+use crate::error;
+use crate::day;
+use crate::inputs;
+use crate::rng::Rng;
+
+/// the default directory that crashing inputs are saved to
+pub const DEFAULT_DIR: &str = "fuzz/crashes";
+
+/// the largest input that mutating will produce
+const MAX_LEN: usize = 4096;
+
+thread_local! {
+    /// if a panic on this thread is being caught by [`run_one`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+
+    /// the message of the last panic caught on this thread
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// bytes that are likely to mean something to one of the parsers
+const INTERESTING: &[&[u8]] = &[
+    b"\n", b"\r\n", b" ", b"\t", b"-", b",", b"[", b"]", b"0", b"1", b"9",
+    b"a", b"Z", b"A", b"X", b"move", b"from", b"to", b"\xc3\xa9", b"\xe2\x82\xac",
+    b"\xff", b"\x00", b"4294967295", b"4294967296", b"18446744073709551615",
+    b"18446744073709551616",
+];
+
+/// an input that made a day panic
+pub struct Crash {
+    pub day: u8,
+    pub input: Vec<u8>,
+    pub message: String,
+}
+
+impl Crash {
+    /// the file name the crash is saved as. the hash keeps the same input
+    /// from being saved twice
+    pub fn file_name(&self) -> String {
+        format!("day{}-{:016x}.txt", self.day, inputs::hash(&self.input))
+    }
+
+    pub fn save(&self, dir: &Path) -> error::Result<PathBuf> {
+        let path = dir.join(self.file_name());
+
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, &self.input)?;
+
+        Ok(path)
+    }
+}
+
+/// replaces the panic hook so that panics caught by [`run_one`] are
+/// recorded instead of printed. any other panic goes to the previous hook
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                LAST_PANIC.set(Some(info.to_string()));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// parses and solves every part of the input, catching any panic. returns
+/// the panic message if there was one. a returned error is not a crash
+pub fn run_one(entry: &day::Entry, input: &[u8]) -> Result<(), String> {
+    install_hook();
+
+    let runner = entry.build();
+
+    CATCHING.set(true);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _ = runner.run(&mut &input[..], runner.parts());
+    }));
+
+    CATCHING.set(false);
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(payload) => Err(LAST_PANIC.take().unwrap_or_else(|| payload_message(payload.as_ref()))),
+    }
+}
+
+fn payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+/// applies a single random change to the data. other is used to splice in
+/// parts of another input
+pub fn mutate(rng: &mut Rng, data: &mut Vec<u8>, other: &[u8]) {
+    match rng.below(8) {
+        // flip a bit
+        0 if !data.is_empty() => {
+            let index = rng.index(data.len());
+            data[index] ^= 1 << rng.below(8);
+        },
+        // replace a byte
+        1 if !data.is_empty() => {
+            let index = rng.index(data.len());
+            data[index] = rng.below(256) as u8;
+        },
+        // insert something interesting
+        2 | 3 => {
+            let index = rng.index(data.len() + 1);
+            let insert = rng.pick(INTERESTING);
+            data.splice(index..index, insert.iter().copied());
+        },
+        // remove a range
+        4 if !data.is_empty() => {
+            let start = rng.index(data.len());
+            let end = start + rng.index((data.len() - start).min(16)) + 1;
+            data.drain(start..end);
+        },
+        // duplicate a range
+        5 if !data.is_empty() => {
+            let start = rng.index(data.len());
+            let end = start + rng.index((data.len() - start).min(64)) + 1;
+            let copy = data[start..end].to_vec();
+            data.splice(end..end, copy);
+        },
+        // splice in part of the other input
+        6 if !other.is_empty() => {
+            let start = rng.index(other.len());
+            let end = start + rng.index((other.len() - start).min(64)) + 1;
+            let index = rng.index(data.len() + 1);
+            data.splice(index..index, other[start..end].iter().copied());
+        },
+        // cut the input short
+        7 if !data.is_empty() => {
+            let len = rng.index(data.len());
+            data.truncate(len);
+        },
+        _ => {
+            data.push(*rng.pick(b"0123456789 \n-,abcXYZ"));
+        }
+    }
+
+    data.truncate(MAX_LEN);
+}
+
+/// where the panic happened. the rest of the message can change with the
+/// input so only this is used to tell crashes apart
+fn location(message: &str) -> &str {
+    message.lines().next().unwrap_or(message)
+}
+
+/// removes as much of the input as possible while it still panics in the
+/// same place so that the saved case is easy to read
+pub fn minimize(entry: &day::Entry, input: &[u8], message: &str) -> Vec<u8> {
+    let mut current = input.to_vec();
+    let mut chunk = current.len().div_ceil(2).max(1);
+
+    loop {
+        let mut start = 0;
+
+        while start < current.len() {
+            let end = (start + chunk).min(current.len());
+            let mut attempt = current.clone();
+            attempt.drain(start..end);
+
+            match run_one(entry, &attempt) {
+                Err(found) if location(&found) == location(message) => current = attempt,
+                _ => start += chunk,
+            }
+        }
+
+        if chunk == 1 {
+            return current;
+        }
+
+        chunk = chunk.div_ceil(2);
+    }
+}
+
+/// the results of fuzzing a single day
+pub struct Report {
+    pub day: u8,
+    pub runs: usize,
+    pub crashes: Vec<Crash>,
+}
+
+/// runs mutated versions of the example of the day, collecting every input
+/// that panics. crashes in the same place are only reported once
+pub fn fuzz_day(entry: &day::Entry, rng: &mut Rng, iterations: usize) -> Report {
+    let seed = entry.build().example().input.as_bytes();
+    let others: Vec<&[u8]> = day::REGISTRY.iter()
+        .map(|other| other.build().example().input.as_bytes())
+        .collect();
+
+    let mut crashes: Vec<Crash> = Vec::new();
+
+    for _ in 0..iterations {
+        let mut data = seed.to_vec();
+
+        for _ in 0..rng.between(1, 8) {
+            let other = rng.pick(&others);
+
+            mutate(rng, &mut data, other);
+        }
+
+        crate::trace!(Trace, "fuzz", "day {} input: {:?}", entry.day, String::from_utf8_lossy(&data));
+
+        if let Err(message) = run_one(entry, &data) {
+            if crashes.iter().any(|crash| location(&crash.message) == location(&message)) {
+                continue;
+            }
+
+            let input = minimize(entry, &data, &message);
+
+            crashes.push(Crash { day: entry.day, input, message });
+        }
+    }
+
+    Report { day: entry.day, runs: iterations, crashes }
+}
+
+/// the saved crash inputs in the directory along with the day they are for.
+/// a missing directory has no crashes
+pub fn saved(dir: &Path) -> error::Result<Vec<(u8, PathBuf)>> {
+    let read = match std::fs::read_dir(dir) {
+        Ok(read) => read,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut rtn = Vec::new();
+
+    for item in read {
+        let path = item?.path();
+
+        let day = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix("day"))
+            .and_then(|stem| stem.split('-').next())
+            .and_then(|day| day.parse::<u8>().ok());
+
+        if let Some(day) = day {
+            rtn.push((day, path));
+        }
+    }
+
+    rtn.sort();
+
+    Ok(rtn)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// every saved crash must now end in an answer or an error
+    #[test]
+    fn saved_crashes() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_DIR);
+
+        for (day, path) in saved(&dir).unwrap() {
+            let entry = day::get(day).unwrap();
+            let input = std::fs::read(&path).unwrap();
+
+            if let Err(message) = run_one(entry, &input) {
+                panic!("{} still panics. {}", path.display(), message);
+            }
+        }
+    }
+
+    #[test]
+    fn no_crashes() {
+        let mut rng = Rng::new(2022);
+
+        for entry in day::REGISTRY {
+            let report = fuzz_day(entry, &mut rng, 500);
+
+            if let Some(crash) = report.crashes.first() {
+                panic!(
+                    "day {} panics on {:?}. {}",
+                    crash.day,
+                    String::from_utf8_lossy(&crash.input),
+                    crash.message
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+/// elves with a random number of food items each. large calories may need
+/// the day to run with a wider --width
+pub struct Day1 {
+    elves: usize,
+    min_items: usize,
+    max_items: usize,
+    max_calories: usize,
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Day1 {
+            elves: 1000,
+            min_items: 1,
+            max_items: 10,
+            max_calories: 10000,
+        }
+    }
+}
+
+impl Generator for Day1 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("elves", cli::ValueKind::Usize, "the number of elves")
+            .default("1000"),
+        cli::Opt::new("min-items", cli::ValueKind::Usize, "the fewest food items an elf carries")
+            .default("1"),
+        cli::Opt::new("max-items", cli::ValueKind::Usize, "the most food items an elf carries")
+            .default("10"),
+        cli::Opt::new("max-calories", cli::ValueKind::Usize, "the most calories a single food item has")
+            .default("10000"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.elves = matches.require("elves")?;
+        self.min_items = matches.require("min-items")?;
+        self.max_items = matches.require("max-items")?;
+        self.max_calories = matches.require("max-calories")?;
+
+        if self.elves == 0 {
+            return Err(invalid_option("elves is 0"));
+        }
+
+        if self.min_items == 0 || self.min_items > self.max_items {
+            return Err(invalid_option("min items must be between 1 and max items"));
+        }
+
+        if self.max_calories == 0 {
+            return Err(invalid_option("max calories is 0"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut input = String::new();
+        let mut totals: Vec<u128> = Vec::with_capacity(self.elves);
+
+        for elf in 0..self.elves {
+            if elf != 0 {
+                input.push('\n');
+            }
+
+            let mut total: u128 = 0;
+
+            for _ in 0..rng.between(self.min_items as u64, self.max_items as u64) {
+                let calories = rng.between(1, self.max_calories as u64);
+
+                input.push_str(&format!("{}\n", calories));
+                total += calories as u128;
+            }
+
+            totals.push(total);
+        }
+
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+
+        Generated {
+            input,
+            answers: [
+                totals[0].to_string(),
+                totals.iter().take(3).sum::<u128>().to_string(),
+            ],
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+/// random rounds of rock paper scissors
+pub struct Day2 {
+    rounds: usize,
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Day2 { rounds: 2500 }
+    }
+}
+
+impl Generator for Day2 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("rounds", cli::ValueKind::Usize, "the number of rounds")
+            .default("2500"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.rounds = matches.require("rounds")?;
+
+        if self.rounds == 0 {
+            return Err(invalid_option("rounds is 0"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut input = String::with_capacity(self.rounds * 4);
+        let mut play_total: u64 = 0;
+        let mut outcome_total: u64 = 0;
+
+        for _ in 0..self.rounds {
+            // 0 is rock, 1 is paper and 2 is scissors. a shape beats the
+            // shape one before it
+            let them = rng.below(3);
+            let column = rng.below(3);
+
+            input.push((b'A' + them as u8) as char);
+            input.push(' ');
+            input.push((b'X' + column as u8) as char);
+            input.push('\n');
+
+            // the column is the shape to play. the outcome is 0 for a loss,
+            // 1 for a draw and 2 for a win
+            let outcome = (column + 4 - them) % 3;
+            play_total += column + 1 + outcome * 3;
+
+            // the column is the outcome to get
+            let shape = (them + column + 2) % 3;
+            outcome_total += shape + 1 + column * 3;
+        }
+
+        Generated {
+            input,
+            answers: [play_total.to_string(), outcome_total.to_string()],
+        }
+    }
+}
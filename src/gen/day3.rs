@@ -0,0 +1,136 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn priority(item: u8) -> u64 {
+    if item.is_ascii_lowercase() {
+        (item - b'a' + 1) as u64
+    } else {
+        (item - b'A' + 27) as u64
+    }
+}
+
+/// groups of three rucksacks that each share one item between their
+/// compartments and one badge between the group
+pub struct Day3 {
+    groups: usize,
+    min_items: usize,
+    max_items: usize,
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Day3 {
+            groups: 100,
+            min_items: 8,
+            max_items: 16,
+        }
+    }
+}
+
+impl Day3 {
+    /// creates a compartment with the required items and the rest picked
+    /// from the pool
+    fn compartment(&self, rng: &mut Rng, size: usize, required: &[u8], pool: &[u8]) -> Vec<u8> {
+        let mut items = required.to_vec();
+
+        while items.len() < size {
+            items.push(*rng.pick(pool));
+        }
+
+        rng.shuffle(&mut items);
+
+        items
+    }
+}
+
+impl Generator for Day3 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("groups", cli::ValueKind::Usize, "the number of groups of three rucksacks")
+            .default("100"),
+        cli::Opt::new("min-items", cli::ValueKind::Usize, "the fewest items in a compartment. at least 2")
+            .default("8"),
+        cli::Opt::new("max-items", cli::ValueKind::Usize, "the most items in a compartment")
+            .default("16"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.groups = matches.require("groups")?;
+        self.min_items = matches.require("min-items")?;
+        self.max_items = matches.require("max-items")?;
+
+        if self.groups == 0 {
+            return Err(invalid_option("groups is 0"));
+        }
+
+        // a compartment needs room for the shared item and the badge
+        if self.min_items < 2 || self.min_items > self.max_items {
+            return Err(invalid_option("min items must be between 2 and max items"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut input = String::new();
+        let mut priority_total: u64 = 0;
+        let mut badge_total: u64 = 0;
+
+        for _ in 0..self.groups {
+            let mut items = ITEMS.to_vec();
+            rng.shuffle(&mut items);
+
+            // the badge is the only item that is in every rucksack of the
+            // group. the rest are split so that no other item is shared
+            // between rucksacks or between the compartments of a rucksack
+            let badge = items[0];
+            let pools: Vec<&[u8]> = items[1..].chunks(items[1..].len() / 6).collect();
+
+            badge_total += priority(badge);
+
+            for rucksack in 0..3 {
+                let first_pool = pools[rucksack * 2];
+                let second_pool = pools[rucksack * 2 + 1];
+                let size = rng.between(self.min_items as u64, self.max_items as u64) as usize;
+
+                // the shared item can be the badge itself
+                let shared = if rng.chance(1, 10) {
+                    badge
+                } else {
+                    *rng.pick(first_pool)
+                };
+
+                let (first, second) = if shared == badge {
+                    (
+                        self.compartment(rng, size, &[shared], first_pool),
+                        self.compartment(rng, size, &[shared], second_pool),
+                    )
+                } else if rng.chance(1, 2) {
+                    (
+                        self.compartment(rng, size, &[shared, badge], first_pool),
+                        self.compartment(rng, size, &[shared], second_pool),
+                    )
+                } else {
+                    (
+                        self.compartment(rng, size, &[shared], first_pool),
+                        self.compartment(rng, size, &[shared, badge], second_pool),
+                    )
+                };
+
+                priority_total += priority(shared);
+
+                input.extend(first.iter().map(|item| *item as char));
+                input.extend(second.iter().map(|item| *item as char));
+                input.push('\n');
+            }
+        }
+
+        Generated {
+            input,
+            answers: [priority_total.to_string(), badge_total.to_string()],
+        }
+    }
+}
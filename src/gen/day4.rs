@@ -0,0 +1,105 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+/// pairs of section ranges where a chosen share of them overlap
+pub struct Day4 {
+    pairs: usize,
+    max_section: u64,
+    overlap: u64,
+}
+
+impl Default for Day4 {
+    fn default() -> Self {
+        Day4 {
+            pairs: 1000,
+            max_section: 99,
+            overlap: 50,
+        }
+    }
+}
+
+impl Generator for Day4 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("pairs", cli::ValueKind::Usize, "the number of pairs")
+            .default("1000"),
+        cli::Opt::new("max-section", cli::ValueKind::Usize, "the highest section id. at least 2")
+            .default("99"),
+        cli::Opt::new("overlap", cli::ValueKind::Usize, "the percent of pairs that overlap")
+            .default("50"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.pairs = matches.require("pairs")?;
+        self.max_section = matches.require::<usize>("max-section")? as u64;
+        self.overlap = matches.require::<usize>("overlap")? as u64;
+
+        if self.pairs == 0 {
+            return Err(invalid_option("pairs is 0"));
+        }
+
+        // two sections are needed to make a pair that does not overlap
+        if self.max_section < 2 || self.max_section > u32::MAX as u64 {
+            return Err(invalid_option(format!("max section must be between 2 and {}", u32::MAX)));
+        }
+
+        if self.overlap > 100 {
+            return Err(invalid_option("overlap is more than 100 percent"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut input = String::new();
+        let mut contained: u64 = 0;
+        let mut overlapping: u64 = 0;
+
+        for _ in 0..self.pairs {
+            let overlaps = rng.chance(self.overlap, 100);
+
+            let (first, second) = if overlaps {
+                // both ranges include a common section
+                let common = rng.between(1, self.max_section);
+
+                (
+                    (rng.between(1, common), rng.between(common, self.max_section)),
+                    (rng.between(1, common), rng.between(common, self.max_section)),
+                )
+            } else {
+                // the first range ends before the second starts
+                let split = rng.between(1, self.max_section - 1);
+                let first_lower = rng.between(1, split);
+                let second_upper = rng.between(split + 1, self.max_section);
+
+                (
+                    (first_lower, rng.between(first_lower, split)),
+                    (rng.between(split + 1, second_upper), second_upper),
+                )
+            };
+
+            // either order is valid
+            let (first, second) = if rng.chance(1, 2) {
+                (first, second)
+            } else {
+                (second, first)
+            };
+
+            if (first.0 <= second.0 && first.1 >= second.1) || (second.0 <= first.0 && second.1 >= first.1) {
+                contained += 1;
+            }
+
+            if overlaps {
+                overlapping += 1;
+            }
+
+            input.push_str(&format!("{}-{},{}-{}\n", first.0, first.1, second.0, second.1));
+        }
+
+        Generated {
+            input,
+            answers: [contained.to_string(), overlapping.to_string()],
+        }
+    }
+}
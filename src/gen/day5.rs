@@ -0,0 +1,137 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+/// a crate layout followed by moves that never empty a stack
+pub struct Day5 {
+    stacks: usize,
+    height: usize,
+    moves: usize,
+}
+
+impl Default for Day5 {
+    fn default() -> Self {
+        Day5 {
+            stacks: 9,
+            height: 8,
+            moves: 500,
+        }
+    }
+}
+
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks.iter()
+        .filter_map(|stack| stack.last())
+        .collect()
+}
+
+impl Generator for Day5 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("stacks", cli::ValueKind::Usize, "the number of stacks")
+            .default("9"),
+        cli::Opt::new("height", cli::ValueKind::Usize, "the most crates a stack starts with. at least 2")
+            .default("8"),
+        cli::Opt::new("moves", cli::ValueKind::Usize, "the number of moves")
+            .default("500"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.stacks = matches.require("stacks")?;
+        self.height = matches.require("height")?;
+        self.moves = matches.require("moves")?;
+
+        // moving between stacks needs at least two of them
+        if self.stacks < 2 {
+            return Err(invalid_option("stacks must be at least 2"));
+        }
+
+        if self.height < 2 {
+            return Err(invalid_option("height must be at least 2"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut stacks: Vec<Vec<char>> = Vec::with_capacity(self.stacks);
+
+        // every stack starts with a crate and the first with two so that
+        // there is always a crate that can be moved
+        for index in 0..self.stacks {
+            let min = if index == 0 { 2 } else { 1 };
+            let height = rng.between(min, self.height as u64);
+
+            stacks.push((0..height).map(|_| (b'A' + rng.below(26) as u8) as char).collect());
+        }
+
+        let mut input = String::new();
+        let tallest = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+        for level in (0..tallest).rev() {
+            let mut line = String::with_capacity(self.stacks * 4);
+
+            for (index, stack) in stacks.iter().enumerate() {
+                if index != 0 {
+                    line.push(' ');
+                }
+
+                match stack.get(level) {
+                    Some(ch) => line.push_str(&format!("[{}]", ch)),
+                    None => line.push_str("   "),
+                }
+            }
+
+            input.push_str(line.trim_end());
+            input.push('\n');
+        }
+
+        let numbers: Vec<String> = (1..=self.stacks)
+            .map(|number| format!("{:^3}", number))
+            .collect();
+
+        input.push_str(numbers.join(" ").trim_end());
+        input.push_str("\n\n");
+
+        let mut single = stacks.clone();
+        let mut multiple = stacks;
+
+        for _ in 0..self.moves {
+            // only stacks with more than one crate can be moved from so that
+            // no stack is ever empty
+            let from_choices: Vec<usize> = (0..self.stacks)
+                .filter(|index| single[*index].len() > 1 && multiple[*index].len() > 1)
+                .collect();
+
+            if from_choices.is_empty() {
+                break;
+            }
+
+            let from = *rng.pick(&from_choices);
+            let mut to = rng.index(self.stacks - 1);
+
+            if to >= from {
+                to += 1;
+            }
+
+            let most = single[from].len().min(multiple[from].len()) - 1;
+            let amount = rng.between(1, most as u64) as usize;
+
+            input.push_str(&format!("move {} from {} to {}\n", amount, from + 1, to + 1));
+
+            for _ in 0..amount {
+                let moved = single[from].pop().unwrap();
+                single[to].push(moved);
+            }
+
+            let split = multiple[from].len() - amount;
+            let moved: Vec<char> = multiple[from].drain(split..).collect();
+            multiple[to].extend(moved);
+        }
+
+        Generated {
+            input,
+            answers: [top_crates(&single), top_crates(&multiple)],
+        }
+    }
+}
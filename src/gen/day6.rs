@@ -0,0 +1,136 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+use crate::gen::{Generator, Generated, invalid_option};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// a signal with the start of packet and start of message markers ending at
+/// chosen offsets
+pub struct Day6 {
+    packet: usize,
+    message: usize,
+    length: usize,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Day6 {
+            packet: 1000,
+            message: 3000,
+            length: 4096,
+        }
+    }
+}
+
+/// pushes filler made from two letters that alternate so that it never
+/// contains more than two unique characters in a row. the first letter is
+/// the one the filler follows so that it cannot complete a marker with
+/// what came before it
+fn filler(rng: &mut Rng, signal: &mut Vec<u8>, len: usize) {
+    let first = signal.last().copied().unwrap_or(LETTERS[0]);
+    let mut second = *rng.pick(LETTERS);
+
+    while second == first {
+        second = *rng.pick(LETTERS);
+    }
+
+    for index in 0..len {
+        signal.push(if index % 2 == 0 { second } else { first });
+    }
+}
+
+/// pushes a marker of unique letters. the first letter is the same as the
+/// one before it so that the marker is not complete until its last letter
+fn marker(rng: &mut Rng, signal: &mut Vec<u8>, len: usize) {
+    let mut letters = LETTERS.to_vec();
+    rng.shuffle(&mut letters);
+
+    if let Some(previous) = signal.last() {
+        let index = letters.iter().position(|letter| letter == previous).unwrap();
+        letters.swap(0, index);
+    }
+
+    signal.extend_from_slice(&letters[..len]);
+}
+
+impl Generator for Day6 {
+    const OPTIONS: &'static [cli::Opt] = &[
+        cli::Opt::new("packet", cli::ValueKind::Usize, "where the start of packet marker ends. at least 4")
+            .default("1000"),
+        cli::Opt::new("message", cli::ValueKind::Usize, "where the start of message marker ends. at least 14 after the packet")
+            .default("3000"),
+        cli::Opt::new("length", cli::ValueKind::Usize, "the length of the signal")
+            .default("4096"),
+    ];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        self.packet = matches.require("packet")?;
+        self.message = matches.require("message")?;
+        self.length = matches.require("length")?;
+
+        if self.packet < 4 {
+            return Err(invalid_option("packet must be at least 4"));
+        }
+
+        // the message marker starts after the packet marker so that any
+        // window across the two has a repeat
+        if self.message < self.packet + 14 {
+            return Err(invalid_option("message must be at least 14 after packet"));
+        }
+
+        if self.length < self.message {
+            return Err(invalid_option("length must be at least message"));
+        }
+
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        let mut signal: Vec<u8> = Vec::with_capacity(self.length + 1);
+
+        filler(rng, &mut signal, self.packet - 4);
+        marker(rng, &mut signal, 4);
+        filler(rng, &mut signal, self.message - 14 - self.packet);
+        marker(rng, &mut signal, 14);
+
+        // anything can come after both markers
+        while signal.len() < self.length {
+            signal.push(*rng.pick(LETTERS));
+        }
+
+        signal.push(b'\n');
+
+        Generated {
+            input: signal.iter().map(|letter| *letter as char).collect(),
+            answers: [self.packet.to_string(), self.message.to_string()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day::day6;
+
+    #[test]
+    fn markers_at_any_offset() {
+        for packet in 4..12 {
+            for gap in 0..4 {
+                let generator = Day6 {
+                    packet,
+                    message: packet + 14 + gap,
+                    length: packet + 14 + gap + 2,
+                };
+
+                for seed in 0..20 {
+                    let generated = generator.generate(&mut Rng::new(seed));
+                    let signal = generated.input.trim_end();
+
+                    assert_eq!(day6::packet_start(signal).unwrap(), generator.packet, "{}", signal);
+                    assert_eq!(day6::message_start(signal).unwrap(), generator.message, "{}", signal);
+                }
+            }
+        }
+    }
+}
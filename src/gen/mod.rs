@@ -0,0 +1,132 @@
+use crate::error;
+use crate::cli;
+use crate::rng::Rng;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+/// a generated puzzle input along with its answers
+pub struct Generated {
+    pub input: String,
+    pub answers: [String; 2],
+}
+
+/// creates valid puzzle inputs for a single day
+///
+/// the answers are worked out from how the input was built instead of by
+/// running the day so that a generated input can be used to check the day.
+pub trait Generator {
+    /// the options that control the size and shape of the input
+    const OPTIONS: &'static [cli::Opt] = &[];
+
+    /// applies the values of the generator options
+    fn configure(&mut self, _matches: &cli::Matches) -> error::Result<()> {
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated;
+}
+
+/// type erased version of [`Generator`] so that generators can be stored in
+/// the registry
+pub trait Gen {
+    fn options(&self) -> &'static [cli::Opt];
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()>;
+
+    fn generate(&self, rng: &mut Rng) -> Generated;
+}
+
+impl<G: Generator> Gen for G {
+    fn options(&self) -> &'static [cli::Opt] {
+        G::OPTIONS
+    }
+
+    fn configure(&mut self, matches: &cli::Matches) -> error::Result<()> {
+        Generator::configure(self, matches)
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Generated {
+        Generator::generate(self, rng)
+    }
+}
+
+/// a registered generator
+pub struct Entry {
+    pub day: u8,
+    build: fn() -> Box<dyn Gen>,
+}
+
+impl Entry {
+    /// creates a new generator with the default options
+    pub fn build(&self) -> Box<dyn Gen> {
+        (self.build)()
+    }
+}
+
+fn build<G>() -> Box<dyn Gen>
+where
+    G: Generator + Default + 'static
+{
+    Box::new(G::default())
+}
+
+/// every day that has a generator
+pub static REGISTRY: &[Entry] = &[
+    Entry { day: 1, build: build::<day1::Day1> },
+    Entry { day: 2, build: build::<day2::Day2> },
+    Entry { day: 3, build: build::<day3::Day3> },
+    Entry { day: 4, build: build::<day4::Day4> },
+    Entry { day: 5, build: build::<day5::Day5> },
+    Entry { day: 6, build: build::<day6::Day6> },
+];
+
+/// finds the generator for the day
+pub fn get(day: u8) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+/// creates the error for an option value that the generator cannot use
+fn invalid_option<M>(message: M) -> error::Error
+where
+    M: Into<String>
+{
+    error::Error::new(error::ErrorKind::InvalidArgument)
+        .with_message(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day;
+
+    /// every generated input must give the answers it reports when run
+    #[test]
+    fn answers_match_days() {
+        for entry in REGISTRY {
+            let generator = entry.build();
+            let day_entry = day::get(entry.day).unwrap();
+
+            for seed in 0..20 {
+                let mut rng = Rng::new(seed);
+                let generated = generator.generate(&mut rng);
+
+                let runner = day_entry.build();
+                let outcome = runner.run(&mut generated.input.as_bytes(), runner.parts())
+                    .unwrap_or_else(|err| panic!("day {} seed {} failed. {}", entry.day, seed, err.summary()));
+
+                for (part, expected) in outcome.parts.iter().zip(&generated.answers) {
+                    assert_eq!(
+                        &part.answer.to_string(),
+                        expected,
+                        "day {} seed {} part {}", entry.day, seed, part.part
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use crate::error;
+
+/// how long to wait on the server before giving up
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// the parts of an `http://` url that are needed to make a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    pub host: String,
+    pub port: u16,
+    /// the path with a leading `/` and no trailing `/`. empty for the root
+    pub path: String,
+}
+
+impl Url {
+    /// parses a url in the form `http://host[:port][/path]`. only plain http
+    /// is supported since there is no tls without a dependency
+    pub fn parse(url: &str) -> error::Result<Url> {
+        let Some(rest) = url.strip_prefix("http://") else {
+            let message = if url.starts_with("https://") {
+                format!("https is not supported. use a local http proxy instead. url: {}", url)
+            } else {
+                format!("expected a url starting with http://. url: {}", url)
+            };
+
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message(message));
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], rest[index..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let Ok(port) = port.parse::<u16>() else {
+                    return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                        .with_message(format!("invalid port in url. url: {}", url)));
+                };
+
+                (host, port)
+            },
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(error::Error::new(error::ErrorKind::InvalidArgument)
+                .with_message(format!("missing host in url. url: {}", url)));
+        }
+
+        Ok(Url {
+            host: host.to_owned(),
+            port,
+            path: path.to_owned(),
+        })
+    }
+}
+
+/// the status and body of a response
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// decodes a body sent with `Transfer-Encoding: chunked`
+fn decode_chunked(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::with_capacity(data.len());
+
+    loop {
+        let line_end = data.windows(2).position(|w| w == b"\r\n")?;
+        let size_str = std::str::from_utf8(&data[..line_end]).ok()?;
+        // chunk extensions come after a `;` and are ignored
+        let size_str = size_str.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_str, 16).ok()?;
+
+        data = &data[(line_end + 2)..];
+
+        if size == 0 {
+            return Some(body);
+        }
+
+        if data.len() < size.checked_add(2)? {
+            return None;
+        }
+
+        body.extend_from_slice(&data[..size]);
+        data = &data[(size + 2)..];
+    }
+}
+
+/// parses the raw bytes of a response
+fn parse_response(raw: &[u8]) -> error::Result<Response> {
+    let malformed = || error::Error::new(error::ErrorKind::Unexpected)
+        .with_message("malformed http response");
+
+    let Some(head_end) = raw.windows(4).position(|w| w == b"\r\n\r\n") else {
+        return Err(malformed());
+    };
+
+    let Ok(head) = std::str::from_utf8(&raw[..head_end]) else {
+        return Err(malformed());
+    };
+    let body = &raw[(head_end + 4)..];
+
+    let mut lines = head.split("\r\n");
+
+    let status = lines.next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|code| code.parse::<u16>().ok());
+
+    let Some(status) = status else {
+        return Err(malformed());
+    };
+
+    let mut chunked = false;
+    let mut content_length: Option<usize> = None;
+
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let value = value.trim();
+
+        if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().ok();
+        }
+    }
+
+    let body = if chunked {
+        let Some(decoded) = decode_chunked(body) else {
+            return Err(malformed());
+        };
+
+        decoded
+    } else if let Some(length) = content_length {
+        if body.len() < length {
+            return Err(malformed());
+        }
+
+        body[..length].to_vec()
+    } else {
+        body.to_vec()
+    };
+
+    Ok(Response { status, body })
+}
+
+/// sends a `GET` request for the path and reads the whole response. the
+/// headers are sent as given after the required ones
+pub fn get(url: &Url, path: &str, headers: &[(&str, &str)]) -> error::Result<Response> {
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))
+        .map_err(|err| error::Error::new(error::ErrorKind::IoError)
+            .with_message(format!("failed to connect to {}:{}", url.host, url.port))
+            .with_error(err))?;
+
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "GET {}{} HTTP/1.1\r\nHost: {}\r\nUser-Agent: aoc2022\r\nConnection: close\r\n",
+        url.path,
+        path,
+        url.host
+    );
+
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    request.push_str("\r\n");
+
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+
+    parse_response(&raw)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// serves the raw responses in order from a local port, one for each
+    /// connection. joining the handle gives the request lines and headers
+    /// that were received
+    pub fn serve(responses: Vec<Vec<u8>>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+
+                loop {
+                    let mut line = String::new();
+
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+
+                    request.push_str(&line);
+                }
+
+                requests.push(request);
+                stream.write_all(&response).unwrap();
+            }
+
+            requests
+        });
+
+        (url, handle)
+    }
+
+    /// a complete response with a content length
+    pub fn response(status: &str, body: &str) -> Vec<u8> {
+        format!("HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body).into_bytes()
+    }
+
+    #[test]
+    fn urls() {
+        let url = Url::parse("http://localhost:8080/base/").unwrap();
+
+        assert_eq!(url, Url { host: "localhost".into(), port: 8080, path: "/base".into() });
+        assert_eq!(Url::parse("http://example.com").unwrap().port, 80);
+
+        for invalid in ["https://example.com", "ftp://example.com", "http://:80", "http://host:port"] {
+            assert!(Url::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn get_sends_headers() {
+        let (url, handle) = serve(vec![response("200 OK", "1\n2\n")]);
+        let url = Url::parse(&url).unwrap();
+
+        let found = get(&url, "/2022/day/1/input", &[("Cookie", "session=abc")]).unwrap();
+        let requests = handle.join().unwrap();
+
+        assert_eq!(found.status, 200);
+        assert_eq!(found.body, b"1\n2\n");
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn chunked() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4;ext=1\r\n1\n2\n\r\n3\r\n34\n\r\n0\r\n\r\n";
+        let found = parse_response(raw).unwrap();
+
+        assert_eq!(found.body, b"1\n2\n34\n");
+
+        // a chunk that is cut short or too large to add up
+        assert!(decode_chunked(b"5\r\nab\r\n0\r\n\r\n").is_none());
+        assert!(decode_chunked(b"ffffffffffffffff\r\nab\r\n").is_none());
+        assert!(decode_chunked(b"zz\r\n").is_none());
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(parse_response(b"HTTP/1.1 abc OK\r\n\r\n").is_err());
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort").is_err());
+        assert_eq!(parse_response(b"HTTP/1.1 404 Not Found\r\n\r\n").unwrap().status, 404);
+    }
+}
@@ -0,0 +1,572 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error;
+use crate::cli;
+use crate::http;
+use crate::toml;
+
+/// the example from the puzzle description
+pub const EXAMPLE: &str = "example";
+/// the personal puzzle input
+pub const REAL: &str = "real";
+/// a large generated input for benchmarking
+pub const STRESS: &str = "stress";
+
+/// the default directory that inputs are stored in
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// checks that a name can be used as a file name in the input directory and
+/// as a section in the manifest and answers files. only ascii letters,
+/// digits, `_` and `-` are allowed
+pub fn check_name(name: &str) -> error::Result<()> {
+    let valid = !name.is_empty() && name.chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(error::Error::new(error::ErrorKind::InvalidArgument)
+            .with_message(format!("invalid input name \"{}\". only letters, digits, _ and - are allowed", name.escape_debug())))
+    }
+}
+
+/// hashes the bytes with 64 bit FNV-1a. this is only meant to notice changes
+/// to a file, not to protect against them
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+/// the recorded hash and size of an input file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub hash: u64,
+    pub size: u64,
+}
+
+impl Fingerprint {
+    pub fn of(bytes: &[u8]) -> Fingerprint {
+        Fingerprint {
+            hash: hash(bytes),
+            size: bytes.len() as u64,
+        }
+    }
+}
+
+/// the fingerprints of the files in the inputs directory
+///
+/// stored in the same toml subset as the answers file
+///
+/// ```toml
+/// [day1.real]
+/// hash = "6f0c2c4b1a8e9d37"
+/// size = 10471
+/// ```
+#[derive(Default)]
+pub struct Manifest {
+    entries: BTreeMap<(u8, String), Fingerprint>,
+}
+
+impl Manifest {
+    pub fn load<P>(path: P) -> error::Result<Manifest>
+    where
+        P: AsRef<Path>
+    {
+        let contents = match std::fs::read_to_string(path.as_ref()) {
+            Ok(c) => c,
+            Err(err) => {
+                return Err(error::Error::new(error::ErrorKind::IoError)
+                    .with_message(format!("failed to read inputs manifest. {}", path.as_ref().display()))
+                    .with_error(err));
+            }
+        };
+
+        let mut partial: BTreeMap<(u8, String), (Option<u64>, Option<u64>)> = BTreeMap::new();
+
+        let entries = toml::parse(&contents)
+            .map_err(|err| err.in_input(path.as_ref().display().to_string()))?;
+
+        for entry in entries {
+            let key = entry.section.split_once('.')
+                .and_then(|(day, name)| Some((day.strip_prefix("day")?.parse::<u8>().ok()?, name.to_owned())));
+
+            let Some(key) = key else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("invalid section in inputs manifest. line {} \"{}\"", entry.line, entry.section)));
+            };
+
+            let fields = partial.entry(key).or_default();
+
+            let valid = match entry.key.as_str() {
+                "hash" => u64::from_str_radix(&entry.value, 16).map(|v| fields.0 = Some(v)).is_ok(),
+                "size" => entry.value.parse::<u64>().map(|v| fields.1 = Some(v)).is_ok(),
+                _ => false,
+            };
+
+            if !valid {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("invalid entry in inputs manifest. line {} \"{}\"", entry.line, entry.key)));
+            }
+        }
+
+        let mut manifest = Manifest::default();
+
+        for ((day, name), fields) in partial {
+            let (Some(hash), Some(size)) = fields else {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("inputs manifest is missing the hash or size of day{}.{}", day, name)));
+            };
+
+            manifest.entries.insert((day, name), Fingerprint { hash, size });
+        }
+
+        Ok(manifest)
+    }
+
+    /// loads the manifest if the file exists, otherwise an empty manifest
+    pub fn load_or_default<P>(path: P) -> error::Result<Manifest>
+    where
+        P: AsRef<Path>
+    {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Manifest::default())
+        }
+    }
+
+    pub fn save<P>(&self, path: P) -> error::Result<()>
+    where
+        P: AsRef<Path>
+    {
+        let mut contents = String::from("# fnv-1a hashes of the input files. update with `aoc2022 inputs record`\n");
+
+        for ((day, name), fingerprint) in &self.entries {
+            contents.push_str(&format!(
+                "\n[day{}.{}]\nhash = \"{:016x}\"\nsize = {}\n",
+                day,
+                name,
+                fingerprint.hash,
+                fingerprint.size
+            ));
+        }
+
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> Option<Fingerprint> {
+        self.entries.get(&(day, name.to_owned())).copied()
+    }
+
+    pub fn set(&mut self, day: u8, name: &str, fingerprint: Fingerprint) {
+        self.entries.insert((day, name.to_owned()), fingerprint);
+    }
+}
+
+/// the state of an input file compared to the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// the file matches the recorded fingerprint
+    Ok,
+    /// the file exists but is not in the manifest
+    Unrecorded,
+    /// the file does not match the recorded fingerprint
+    Modified,
+    /// the file does not exist
+    Missing,
+    /// the file exists but cannot be used as an input. holds the reason
+    Invalid(String),
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ok => f.write_str("ok"),
+            Status::Unrecorded => f.write_str("unrecorded"),
+            Status::Modified => f.write_str("modified"),
+            Status::Missing => f.write_str("missing"),
+            Status::Invalid(reason) => write!(f, "invalid, {}", reason),
+        }
+    }
+}
+
+/// checks that the contents can be used as a puzzle input
+pub fn validate(contents: &[u8]) -> Result<(), String> {
+    if contents.is_empty() {
+        return Err(String::from("the file is empty"));
+    }
+
+    if let Err(err) = std::str::from_utf8(contents) {
+        return Err(format!("the file is not valid utf-8 after byte {}", err.valid_up_to()));
+    }
+
+    Ok(())
+}
+
+/// a source of puzzle inputs that are not in the inputs directory yet
+pub trait Fetch {
+    fn fetch(&self, day: u8) -> error::Result<Vec<u8>>;
+}
+
+/// fetches inputs from an advent of code style server
+///
+/// the input for a day is requested from `{base_url}/2022/day/{day}/input`
+/// with the session token as a cookie. the base url is only checked when an
+/// input is fetched so that a bad url does not stop inputs that are already
+/// stored from being used.
+pub struct HttpFetch {
+    base_url: Option<String>,
+    session: String,
+}
+
+impl HttpFetch {
+    /// there is no default base url since only plain http is supported.
+    /// fetching without one is an error
+    pub fn new(base_url: Option<String>, session: String) -> Self {
+        HttpFetch {
+            base_url,
+            session,
+        }
+    }
+}
+
+impl Fetch for HttpFetch {
+    fn fetch(&self, day: u8) -> error::Result<Vec<u8>> {
+        let Some(base_url) = &self.base_url else {
+            return Err(error::Error::new(error::ErrorKind::MissingArgument)
+                .with_message("no server to fetch inputs from. use --base-url or AOC_BASE_URL with an http:// url"));
+        };
+
+        let url = http::Url::parse(base_url)?;
+        let cookie = format!("session={}", self.session);
+        let response = http::get(
+            &url,
+            &format!("/2022/day/{}/input", day),
+            &[("Cookie", &cookie)]
+        )?;
+
+        if response.status != 200 {
+            return Err(error::Error::new(error::ErrorKind::IoError)
+                .with_message(format!("failed to fetch the input for day {}. status: {}", day, response.status)));
+        }
+
+        Ok(response.body)
+    }
+}
+
+/// the inputs directory. each day has its own directory with a text file for
+/// each named input, `inputs/day1/real.txt`
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Store::new(DEFAULT_DIR)
+    }
+}
+
+impl Store {
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>
+    {
+        Store { root: root.into() }
+    }
+
+    /// the canonical path of a named input for a day
+    pub fn path(&self, day: u8, name: &str) -> PathBuf {
+        let mut path = self.root.clone();
+        path.push(format!("day{}", day));
+        path.push(format!("{}.txt", name));
+        path
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.root.join("manifest.toml")
+    }
+
+    pub fn manifest(&self) -> error::Result<Manifest> {
+        Manifest::load_or_default(self.manifest_path())
+    }
+
+    /// the names of the inputs stored for a day, sorted
+    pub fn names(&self, day: u8) -> error::Result<Vec<String>> {
+        let dir = self.root.join(format!("day{}", day));
+
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = Vec::new();
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().into_owned());
+                }
+            }
+        }
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// compares a named input against the manifest
+    pub fn status(&self, manifest: &Manifest, day: u8, name: &str) -> error::Result<Status> {
+        let path = self.path(day, name);
+
+        if !path.is_file() {
+            return Ok(Status::Missing);
+        }
+
+        let contents = std::fs::read(&path)?;
+
+        if let Err(reason) = validate(&contents) {
+            return Ok(Status::Invalid(reason));
+        }
+
+        Ok(match manifest.get(day, name) {
+            Some(recorded) if recorded == Fingerprint::of(&contents) => Status::Ok,
+            Some(_) => Status::Modified,
+            None => Status::Unrecorded,
+        })
+    }
+
+    /// records the current fingerprint of a named input in the manifest
+    pub fn record(&self, manifest: &mut Manifest, day: u8, name: &str) -> error::Result<()> {
+        check_name(name)?;
+
+        let contents = std::fs::read(self.path(day, name))?;
+
+        if let Err(reason) = validate(&contents) {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("day{}.{} cannot be recorded. {}", day, name, reason)));
+        }
+
+        manifest.set(day, name, Fingerprint::of(&contents));
+
+        Ok(())
+    }
+
+    /// fetches the real input for a day, saves it and records it in the
+    /// manifest
+    pub fn fetch(&self, fetcher: &dyn Fetch, day: u8) -> error::Result<PathBuf> {
+        let contents = fetcher.fetch(day)?;
+
+        if let Err(reason) = validate(&contents) {
+            return Err(error::Error::new(error::ErrorKind::BadInput)
+                .with_message(format!("the fetched input for day {} is invalid. {}", day, reason)));
+        }
+
+        let path = self.path(day, REAL);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&path, &contents)?;
+
+        let mut manifest = self.manifest()?;
+        manifest.set(day, REAL, Fingerprint::of(&contents));
+        manifest.save(self.manifest_path())?;
+
+        Ok(path)
+    }
+
+    /// finds the path of a named input. a missing real input is fetched if
+    /// a fetcher is given. a warning is printed if the input no longer
+    /// matches the manifest
+    pub fn resolve(&self, day: u8, name: &str, fetcher: Option<&dyn Fetch>) -> error::Result<PathBuf> {
+        check_name(name)?;
+
+        let path = self.path(day, name);
+
+        if !path.is_file() {
+            return match fetcher {
+                Some(fetcher) if name == REAL => self.fetch(fetcher, day),
+                _ => Err(error::Error::new(error::ErrorKind::MissingArgument)
+                    .with_message(format!("no {} input found for day {}. expected: {}", name, day, path.display())))
+            };
+        }
+
+        let manifest = self.manifest()?;
+
+        match self.status(&manifest, day, name)? {
+            Status::Modified => {
+                eprintln!(
+                    "warning: {} does not match the inputs manifest. run `aoc2022 inputs record {}` if the change is intended",
+                    path.display(),
+                    day
+                );
+            },
+            Status::Invalid(reason) => {
+                return Err(error::Error::new(error::ErrorKind::BadInput)
+                    .with_message(format!("{} cannot be used as an input. {}", path.display(), reason)));
+            },
+            _ => {}
+        }
+
+        Ok(path)
+    }
+}
+
+/// the options for finding and fetching inputs
+pub const OPTIONS: &[cli::Opt] = &[
+    cli::Opt::new("input-dir", cli::ValueKind::Path, "the directory that named inputs are stored in")
+        .default(DEFAULT_DIR),
+    cli::Opt::new("session", cli::ValueKind::String, "the session token used to fetch missing real inputs. defaults to AOC_SESSION"),
+    cli::Opt::new("base-url", cli::ValueKind::String, "the http:// server to fetch inputs from. defaults to AOC_BASE_URL"),
+];
+
+/// the store from the values of [`OPTIONS`]
+pub fn store(matches: &cli::Matches) -> error::Result<Store> {
+    let root: PathBuf = matches.require("input-dir")?;
+
+    Ok(Store::new(root))
+}
+
+/// the fetcher from the values of [`OPTIONS`]. there is only a fetcher if a
+/// session token is given
+pub fn fetcher(matches: &cli::Matches) -> error::Result<Option<Box<dyn Fetch>>> {
+    let session = match matches.get::<String>("session")? {
+        Some(session) => session,
+        None => match std::env::var("AOC_SESSION") {
+            Ok(session) if !session.is_empty() => session,
+            _ => return Ok(None),
+        }
+    };
+
+    let base_url = match matches.get::<String>("base-url")? {
+        Some(url) => Some(url),
+        None => std::env::var("AOC_BASE_URL")
+            .ok()
+            .filter(|url| !url.is_empty()),
+    };
+
+    Ok(Some(Box::new(HttpFetch::new(base_url, session))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::http::test::{serve, response};
+
+    /// an empty store in its own temporary directory
+    fn temp_store(name: &str) -> (PathBuf, Store) {
+        let root = std::env::temp_dir().join(format!("aoc2022-inputs-{}-{}", name, std::process::id()));
+
+        let _ = std::fs::remove_dir_all(&root);
+
+        (root.clone(), Store::new(root))
+    }
+
+    #[test]
+    fn names() {
+        for name in ["real", "stress", "big_2", "a-b"] {
+            assert!(check_name(name).is_ok(), "{}", name);
+        }
+
+        let store = Store::new(std::env::temp_dir().join(format!("aoc2022-inputs-names-{}", std::process::id())));
+
+        for name in ["", "../../tmp/x", "a.b", "a]b", "a\nb", "a b"] {
+            let err = store.resolve(1, name, None).unwrap_err();
+
+            assert_eq!(err.kind, error::ErrorKind::InvalidArgument, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let (root, store) = temp_store("fetch");
+        // only one response so a second fetch would fail to connect
+        let (url, handle) = serve(vec![response("200 OK", "1000\n2000\n")]);
+        let fetcher = HttpFetch::new(Some(url), String::from("token"));
+
+        let path = store.resolve(1, REAL, Some(&fetcher)).unwrap();
+        let requests = handle.join().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert!(requests[0].contains("Cookie: session=token\r\n"));
+        assert_eq!(store.status(&store.manifest().unwrap(), 1, REAL).unwrap(), Status::Ok);
+        assert_eq!(store.resolve(1, REAL, Some(&fetcher)).unwrap(), path);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn fetch_failures() {
+        let (root, store) = temp_store("failures");
+        let (url, handle) = serve(vec![
+            response("404 Not Found", "not found"),
+            response("200 OK", ""),
+        ]);
+        let fetcher = HttpFetch::new(Some(url), String::from("token"));
+
+        assert!(store.resolve(1, REAL, Some(&fetcher)).is_err());
+        // an empty body is not a usable input
+        assert!(store.resolve(1, REAL, Some(&fetcher)).is_err());
+
+        handle.join().unwrap();
+
+        assert!(!store.path(1, REAL).exists());
+
+        // without a session there is nothing to fetch with
+        let err = store.resolve(1, REAL, None).unwrap_err();
+
+        assert!(matches!(err.kind, error::ErrorKind::MissingArgument));
+
+        // without a base url or with one that cannot be used
+        for base_url in [None, Some(String::from("https://adventofcode.com"))] {
+            assert!(store.resolve(1, REAL, Some(&HttpFetch::new(base_url, String::from("token")))).is_err());
+        }
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn manifest_mismatch() {
+        let (root, store) = temp_store("manifest");
+        let path = store.path(2, REAL);
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "A Y\n").unwrap();
+
+        let mut manifest = store.manifest().unwrap();
+
+        assert_eq!(store.status(&manifest, 2, REAL).unwrap(), Status::Unrecorded);
+
+        store.record(&mut manifest, 2, REAL).unwrap();
+        manifest.save(store.manifest_path()).unwrap();
+
+        let manifest = Manifest::load(store.manifest_path()).unwrap();
+
+        assert_eq!(store.status(&manifest, 2, REAL).unwrap(), Status::Ok);
+
+        std::fs::write(&path, "A Z\n").unwrap();
+
+        assert_eq!(store.status(&manifest, 2, REAL).unwrap(), Status::Modified);
+        // a modified input is still used with a warning
+        assert_eq!(store.resolve(2, REAL, None).unwrap(), path);
+
+        std::fs::write(&path, b"A \xff\n").unwrap();
+
+        assert!(matches!(store.status(&manifest, 2, REAL).unwrap(), Status::Invalid(_)));
+        assert!(store.resolve(2, REAL, None).is_err());
+        assert!(store.status(&manifest, 3, REAL).unwrap() == Status::Missing);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
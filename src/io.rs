@@ -1,26 +1,300 @@
-use std::io::{Lines, BufReader, Read, BufRead};
+use std::io::BufRead;
 
+use crate::error;
+
+/// how the lines of an input are cleaned up before they are parsed
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Normalize {
+    /// removes spaces and tabs from the end of each line
+    pub trim_trailing: bool,
+}
+
+impl Normalize {
+    /// only removes the line ending
+    pub const LINE_ENDING: Normalize = Normalize { trim_trailing: false };
+
+    /// removes the line ending and any trailing whitespace
+    pub const TRIM: Normalize = Normalize { trim_trailing: true };
+
+    /// removes the `\r` left over from a `\r\n` line ending and, if
+    /// requested, the trailing whitespace of the line. the `\n` must already
+    /// be removed
+    pub fn apply<'a>(&self, line: &'a [u8]) -> &'a [u8] {
+        let mut end = line.len();
+
+        while end > 0 {
+            match line[end - 1] {
+                b'\r' => end -= 1,
+                b' ' | b'\t' if self.trim_trailing => end -= 1,
+                _ => break,
+            }
+        }
+
+        &line[..end]
+    }
+}
+
+/// reads lines one at a time from a reader, normalizing them along the way
+///
+/// this is only kept as the baseline for `benches/lines.rs`. no day uses it,
+/// they all read the input with [`read_all`] and go through it with
+/// [`StrLines`], so it is hidden from the docs and is not meant to be used
+/// elsewhere.
+///
+/// each line is a new string so this is slower than [`StrLines`] but does
+/// not need the whole input in memory. the count is the number of lines that
+/// have been returned so after a line is returned it is the line number of
+/// that line.
+#[doc(hidden)]
 pub struct LineReader<T> {
     count: usize,
-    lines: Lines<BufReader<T>>
+    inner: T,
+    buffer: Vec<u8>,
+    normalize: Normalize,
+    ended_with_newline: bool,
 }
 
-impl<T: Read> LineReader<T> {
-    pub fn new(inner: BufReader<T>) -> Self {
-        LineReader { count: 0, lines: inner.lines() }
+impl<T: BufRead> LineReader<T> {
+    pub fn new(inner: T) -> Self {
+        LineReader {
+            count: 0,
+            inner,
+            buffer: Vec::new(),
+            normalize: Normalize::LINE_ENDING,
+            ended_with_newline: false,
+        }
     }
 
-    pub fn next_line(&mut self) -> std::io::Result<Option<String>> {
-        self.count += 1;
+    pub fn normalize(mut self, normalize: Normalize) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn next_line(&mut self) -> error::Result<Option<String>> {
+        self.buffer.clear();
 
-        let Some(result) = self.lines.next() else {
+        if self.inner.read_until(b'\n', &mut self.buffer)? == 0 {
             return Ok(None);
-        };
+        }
+
+        self.count += 1;
+        self.ended_with_newline = self.buffer.last() == Some(&b'\n');
+
+        let line = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+        let line = self.normalize.apply(line);
+
+        match std::str::from_utf8(line) {
+            Ok(valid) => Ok(Some(valid.to_owned())),
+            Err(err) => Err(invalid_utf8(self.count, line, err)),
+        }
+    }
+
+    /// the line number of the last line returned
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
 
-        Ok(Some(result?))
+    /// if the last line read was ended by a newline. only meaningful once
+    /// [`LineReader::next_line`] returns `None`
+    pub fn ended_with_newline(&self) -> bool {
+        self.ended_with_newline
     }
+}
+
+/// creates the error for a line that is not valid utf-8
+fn invalid_utf8(line_number: usize, line: &[u8], err: std::str::Utf8Error) -> error::Error {
+    let valid = err.valid_up_to();
+
+    // show the line lossy so that the caret still lines up with the valid
+    // part of the line. the first invalid sequence becomes the replacement
+    // character
+    let shown = String::from_utf8_lossy(line);
+
+    error::build::bad_input(error::Diagnostic::new(
+        line_number,
+        shown,
+        valid..(valid + char::REPLACEMENT_CHARACTER.len_utf8()),
+        "expected valid utf-8"
+    ))
+}
+
+/// reads the rest of the reader into a single buffer so that the lines can
+/// be borrowed from it instead of allocating a string for each line
+pub fn read_all(reader: &mut dyn BufRead) -> error::Result<String> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+
+    match String::from_utf8(buffer) {
+        Ok(valid) => Ok(valid),
+        Err(err) => {
+            let bytes = err.as_bytes();
+            let valid = err.utf8_error().valid_up_to();
+
+            let line_start = bytes[..valid].iter()
+                .rposition(|byte| *byte == b'\n')
+                .map(|index| index + 1)
+                .unwrap_or(0);
+            let line_end = bytes[line_start..].iter()
+                .position(|byte| *byte == b'\n')
+                .map(|index| line_start + index)
+                .unwrap_or(bytes.len());
+            let line_number = bytes[..line_start].iter()
+                .filter(|byte| **byte == b'\n')
+                .count() + 1;
+            let line = &bytes[line_start..line_end];
+
+            let err = std::str::from_utf8(line).unwrap_err();
+
+            Err(invalid_utf8(line_number, line, err))
+        }
+    }
+}
 
-    pub fn get_count(&self) -> &usize {
-        &self.count
+/// finds the next line in the data starting at the position. returns the
+/// normalized range of the line and the position of the line after it
+fn split_line(data: &[u8], position: usize, normalize: Normalize) -> Option<(std::ops::Range<usize>, usize)> {
+    if position >= data.len() {
+        return None;
     }
-} 
\ No newline at end of file
+
+    let (end, next) = match data[position..].iter().position(|byte| *byte == b'\n') {
+        Some(index) => (position + index, position + index + 1),
+        None => (data.len(), data.len()),
+    };
+
+    let line = normalize.apply(&data[position..end]);
+
+    Some((position..(position + line.len()), next))
+}
+
+/// the lines of a string buffer along with their line number, starting at 1
+///
+/// the line ending is not included in the line and, the same as
+/// [`str::lines`], a newline at the end of the buffer does not produce an
+/// empty last line.
+pub struct StrLines<'a> {
+    count: usize,
+    position: usize,
+    data: &'a str,
+    normalize: Normalize,
+}
+
+impl<'a> StrLines<'a> {
+    pub fn new(data: &'a str) -> Self {
+        StrLines {
+            count: 0,
+            position: 0,
+            data,
+            normalize: Normalize::LINE_ENDING,
+        }
+    }
+
+    pub fn normalize(mut self, normalize: Normalize) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// the number of lines that have been returned
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    /// if the buffer ends with a newline
+    pub fn ends_with_newline(&self) -> bool {
+        self.data.ends_with('\n')
+    }
+}
+
+impl<'a> Iterator for StrLines<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // only ascii bytes are removed so the range is always on a char
+        // boundary
+        let (range, next) = split_line(self.data.as_bytes(), self.position, self.normalize)?;
+
+        self.position = next;
+        self.count += 1;
+
+        Some((self.count, &self.data[range]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CRLF: &str = "one \r\ntwo\t\r\n\r\nthree";
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Normalize::LINE_ENDING.apply(b"a b \r"), b"a b ");
+        assert_eq!(Normalize::TRIM.apply(b"a b \t\r"), b"a b");
+        assert_eq!(Normalize::TRIM.apply(b" \r\r"), b"");
+        // only the end of the line is changed
+        assert_eq!(Normalize::TRIM.apply(b"\ra b"), b"\ra b");
+    }
+
+    #[test]
+    fn str_lines() {
+        let mut lines = StrLines::new(CRLF).normalize(Normalize::TRIM);
+
+        assert_eq!(lines.by_ref().collect::<Vec<_>>(), vec![(1, "one"), (2, "two"), (3, ""), (4, "three")]);
+        assert_eq!(lines.get_count(), 4);
+        assert!(!lines.ends_with_newline());
+
+        let lines = StrLines::new("a\n\nb\n");
+
+        assert!(lines.ends_with_newline());
+        assert_eq!(lines.collect::<Vec<_>>(), vec![(1, "a"), (2, ""), (3, "b")]);
+        assert_eq!(StrLines::new("").count(), 0);
+    }
+
+    #[test]
+    fn line_reader() {
+        let mut reader = LineReader::new(CRLF.as_bytes()).normalize(Normalize::TRIM);
+        let mut lines = Vec::new();
+
+        while let Some(line) = reader.next_line().unwrap() {
+            lines.push((reader.get_count(), line));
+        }
+
+        assert_eq!(lines, vec![
+            (1, String::from("one")),
+            (2, String::from("two")),
+            (3, String::new()),
+            (4, String::from("three")),
+        ]);
+        // the count does not move past the last line at the end
+        assert_eq!(reader.get_count(), 4);
+        assert!(!reader.ended_with_newline());
+
+        let mut reader = LineReader::new(&b"a \n"[..]);
+
+        assert_eq!(reader.next_line().unwrap().as_deref(), Some("a "));
+        assert_eq!(reader.next_line().unwrap(), None);
+        assert!(reader.ended_with_newline());
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let data = b"ok\nab\xffcd\n";
+
+        let err = read_all(&mut &data[..]).unwrap_err();
+        let diagnostic = err.diagnostic.unwrap();
+
+        assert_eq!(diagnostic.line_number, 2);
+        assert_eq!(diagnostic.span, 2..5);
+        assert_eq!(diagnostic.column(), 3);
+        assert_eq!(diagnostic.reason, "expected valid utf-8");
+
+        let mut reader = LineReader::new(&data[..]);
+
+        assert!(reader.next_line().is_ok());
+
+        let diagnostic = reader.next_line().unwrap_err().diagnostic.unwrap();
+
+        assert_eq!((diagnostic.line_number, diagnostic.span), (2, 2..5));
+        assert_eq!(read_all(&mut &b"fine\n"[..]).unwrap(), "fine\n");
+    }
+}
@@ -0,0 +1,18 @@
+pub mod error;
+pub mod io;
+pub mod cli;
+pub mod day;
+pub mod answers;
+pub mod command;
+pub mod output;
+pub mod stats;
+pub mod toml;
+pub mod bench;
+pub mod trace;
+pub mod http;
+pub mod inputs;
+pub mod rng;
+pub mod fuzz;
+pub mod gen;
+pub mod top_k;
+pub mod bigint;
@@ -1,41 +1,126 @@
-mod error;
-mod io;
-mod cli;
-mod day;
-
-fn main() {
-    let mut args = std::env::args();
-    args.next();
-
-    let Some(day) = args.next() else {
-        println!("no day specified");
-        return;
-    };
+use aoc2022::cli;
+use aoc2022::command;
+use aoc2022::error;
+use aoc2022::output::{Output, Format, Record};
+
+/// the options that come before the command
+const GLOBAL_OPTIONS: &[cli::Opt] = &[
+    cli::Opt::new("format", cli::ValueKind::Choice(&["text", "json", "csv"]), "the format to write results in")
+        .default("text"),
+    cli::Opt::new("backtrace", cli::ValueKind::Flag, "shows the full chain of errors that caused a failure"),
+    cli::HELP,
+];
+
+fn print_help() {
+    print!(
+        "usage: aoc2022 [options] <command> [command options]\n\n\
+        commands:\n  \
+            <day>  runs a single day. see `aoc2022 <day> --help`\n  \
+            all    runs every day with its puzzle input\n  \
+            fuzz   looks for inputs that panic. see `aoc2022 fuzz --help`\n  \
+            gen    writes a synthetic input for a day. see `aoc2022 gen --help`\n  \
+            inputs manages the inputs directory. see `aoc2022 inputs --help`\n  \
+            list   lists the registered days\n\n{}\n\
+        exit codes:\n  \
+            0  success\n  \
+            2  invalid argument\n  \
+            3  missing argument\n  \
+            4  bad input\n  \
+            5  check failed\n  \
+            6  io error\n  \
+            7  unexpected error\n",
+        cli::help_section("options", GLOBAL_OPTIONS)
+    );
+}
+
+/// prints the error to stderr. only the direct source of the error is shown
+/// unless backtrace is set, in which case every source in the chain is
+fn print_error(err: &error::Error, backtrace: bool) {
+    eprintln!("{}", err);
+
+    if backtrace {
+        let mut chain = err.chain().peekable();
+
+        if chain.peek().is_some() {
+            eprintln!("\ncaused by:");
 
-    let result = match day.as_str() {
-        "1" => day::day1::run(args),
-        "2" => day::day2::run(args),
-        "3" => day::day3::run(args),
-        "4" => day::day4::run(args),
-        "5" => day::day5::run(args),
-        "6" => day::day6::run(args),
-        _ => {
-            Err(error::Error::new(error::ErrorKind::InvalidArgument)
-                .with_message(format!("unknown day specified. given: {}", day)))
+            for (index, source) in chain.enumerate() {
+                eprintln!("  {}: {}", index, source);
+            }
         }
+    } else if let Some(source) = &err.source {
+        eprintln!("  caused by: {}", source);
+    }
+
+    if let Some(diagnostic) = &err.diagnostic {
+        eprintln!("{}", diagnostic.render());
+    }
+}
+
+fn exit_code(err: &error::Error) -> std::process::ExitCode {
+    std::process::ExitCode::from(err.kind.exit_code())
+}
+
+fn run<I>(command: Option<&String>, args: &mut I, out: &mut Output) -> error::Result<()>
+where
+    I: Iterator<Item = String>
+{
+    let Some(arg) = command else {
+        return Err(error::Error::new(error::ErrorKind::MissingArgument)
+            .with_message("no day specified"));
     };
 
-    if let Err(err) = result {
-        print!("{}", err.kind);
+    match arg.as_str() {
+        "list" => {
+            command::list();
+            Ok(())
+        },
+        "all" => command::all(args, out),
+        "inputs" => command::inputs(args),
+        "fuzz" => command::fuzz(args),
+        "gen" => command::gen(args),
+        _ => command::run(arg.clone(), args, out)
+    }
+}
+
+fn main() -> std::process::ExitCode {
+    let mut args = std::env::args().skip(1);
 
-        if let Some(message) = err.message {
-            print!(": {}", message);
+    let globals = match cli::parse(&[GLOBAL_OPTIONS], &mut args, true) {
+        Ok(matches) => matches,
+        Err(err) => {
+            print_error(&err, false);
+
+            return exit_code(&err);
         }
+    };
+
+    let backtrace = globals.flag("backtrace");
 
-        if let Some(source) = err.source {
-            print!("\n{}", source);
+    let command = globals.positionals().first();
+
+    if command.is_none() && globals.flag("help") {
+        print_help();
+
+        return std::process::ExitCode::SUCCESS;
+    }
+
+    let format = globals.get::<Format>("format")
+        .ok()
+        .flatten()
+        .unwrap_or(Format::Text);
+    let mut out = Output::new(format);
+
+    if let Err(err) = run(command, &mut args, &mut out) {
+        if format != Format::Text {
+            let day = command.and_then(|arg| arg.parse::<u8>().ok());
+            let _ = out.write_record(&Record::from_error(day, None, &err));
+        } else {
+            print_error(&err, backtrace);
         }
 
-        print!("\n");
+        return exit_code(&err);
     }
+
+    std::process::ExitCode::SUCCESS
 }
@@ -0,0 +1,192 @@
+use std::io::Write;
+use std::time::Duration;
+
+use crate::error;
+use crate::day::Part;
+
+/// the formats that results can be written in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn try_from_str<S>(parse: S) -> Option<Format>
+    where
+        S: AsRef<str>
+    {
+        match parse.as_ref() {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None
+        }
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::try_from_str(s).ok_or(())
+    }
+}
+
+/// a single result to be written. days that ran produce one record per part
+/// and failures produce a record with the error and no part
+#[derive(Default)]
+pub struct Record {
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub answer: Option<String>,
+    pub status: &'static str,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+    pub input: Option<String>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// creates a record for an error that stopped a day from running
+    pub fn from_error(day: Option<u8>, input: Option<String>, err: &error::Error) -> Self {
+        Record {
+            day,
+            input,
+            status: "error",
+            error: Some(err.summary()),
+            ..Default::default()
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,part,answer,status,parse_ns,solve_ns,input,error";
+
+/// escapes a string to be placed inside of a json string
+fn json_string(value: &str) -> String {
+    let mut rtn = String::with_capacity(value.len() + 2);
+    rtn.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => rtn.push_str("\\\""),
+            '\\' => rtn.push_str("\\\\"),
+            '\n' => rtn.push_str("\\n"),
+            '\r' => rtn.push_str("\\r"),
+            '\t' => rtn.push_str("\\t"),
+            c if (c as u32) < 0x20 => rtn.push_str(&format!("\\u{:04x}", c as u32)),
+            c => rtn.push(c)
+        }
+    }
+
+    rtn.push('"');
+    rtn
+}
+
+/// quotes a csv field if it contains characters that would break the row
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// writes records in the requested format. text output is left to the
+/// commands since each one has its own layout
+pub struct Output {
+    format: Format,
+    wrote_header: bool,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Self {
+        Output {
+            format,
+            wrote_header: false,
+        }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn write_record(&mut self, record: &Record) -> error::Result<()> {
+        let mut stdout = std::io::stdout().lock();
+
+        match self.format {
+            Format::Text => {},
+            Format::Json => {
+                let opt_str = |v: &Option<String>| v.as_deref()
+                    .map(json_string)
+                    .unwrap_or_else(|| String::from("null"));
+                let opt_num = |v: Option<String>| v.unwrap_or_else(|| String::from("null"));
+
+                writeln!(
+                    stdout,
+                    "{{\"day\":{},\"part\":{},\"answer\":{},\"status\":{},\"parse_ns\":{},\"solve_ns\":{},\"input\":{},\"error\":{}}}",
+                    opt_num(record.day.map(|v| v.to_string())),
+                    opt_num(record.part.map(|v| v.to_string())),
+                    opt_str(&record.answer),
+                    json_string(record.status),
+                    opt_num(record.parse_time.map(|v| v.as_nanos().to_string())),
+                    opt_num(record.solve_time.map(|v| v.as_nanos().to_string())),
+                    opt_str(&record.input),
+                    opt_str(&record.error),
+                )?;
+            },
+            Format::Csv => {
+                if !self.wrote_header {
+                    writeln!(stdout, "{}", CSV_HEADER)?;
+
+                    self.wrote_header = true;
+                }
+
+                let opt = |v: Option<String>| v.as_deref()
+                    .map(csv_field)
+                    .unwrap_or_default();
+
+                writeln!(
+                    stdout,
+                    "{},{},{},{},{},{},{},{}",
+                    opt(record.day.map(|v| v.to_string())),
+                    opt(record.part.map(|v| v.to_string())),
+                    opt(record.answer.clone()),
+                    csv_field(record.status),
+                    opt(record.parse_time.map(|v| v.as_nanos().to_string())),
+                    opt(record.solve_time.map(|v| v.as_nanos().to_string())),
+                    opt(record.input.clone()),
+                    opt(record.error.clone()),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_escaping() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(json_string("C:\\inputs"), "\"C:\\\\inputs\"");
+        assert_eq!(json_string("a\nb\r\tc"), "\"a\\nb\\r\\tc\"");
+        assert_eq!(json_string("\u{0}\u{1b}"), "\"\\u0000\\u001b\"");
+        assert_eq!(json_string("\u{7f}é"), "\"\u{7f}é\"");
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+        assert_eq!(csv_field(""), "");
+    }
+}
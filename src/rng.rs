@@ -0,0 +1,117 @@
+/// a small seeded pseudo random number generator (splitmix64)
+///
+/// this is not suitable for anything that needs to be unpredictable. the same
+/// seed always produces the same sequence so that any run can be reproduced
+/// from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// a seed taken from the current time for when one is not given
+    pub fn seed_from_time() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// a value from 0 up to but not including bound. bound must not be 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound != 0, "bound must not be 0");
+
+        // widening multiply to map the full range onto the bound. the bias
+        // is at most bound / 2^64 which is fine for generating inputs
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// a value in the inclusive range
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "low must not be greater than high");
+
+        match (high - low).checked_add(1) {
+            Some(bound) => low + self.below(bound),
+            None => self.next_u64(),
+        }
+    }
+
+    /// an index into a slice with the given length. len must not be 0
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// true with a probability of numerator / denominator
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// a random item from the slice. the slice must not be empty
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// shuffles the slice in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.index(index + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let value = rng.between(3, 8);
+
+            assert!((3..=8).contains(&value));
+
+            seen[(value - 3) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|v| *v), "every value should be produced");
+        // the full range must not overflow
+        rng.between(0, u64::MAX);
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..50).collect();
+
+        rng.shuffle(&mut items);
+        items.sort();
+
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,129 @@
+/// summary statistics for a set of samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub stddev: f64,
+}
+
+/// finds the value at the given percentile of sorted samples using linear
+/// interpolation between the closest ranks
+pub fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let rank = (pct / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    if lower == upper {
+        sorted[lower]
+    } else {
+        sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+    }
+}
+
+impl Summary {
+    /// calculates the summary of the given samples. the samples will be
+    /// sorted in place
+    pub fn from_samples(samples: &mut [f64]) -> Option<Summary> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort_by(f64::total_cmp);
+
+        let count = samples.len();
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        let variance = samples.iter()
+            .map(|v| (v - mean) * (v - mean))
+            .sum::<f64>() / count as f64;
+
+        Some(Summary {
+            count,
+            min: samples[0],
+            max: samples[count - 1],
+            median: percentile(samples, 50.0),
+            mean,
+            p95: percentile(samples, 95.0),
+            stddev: variance.sqrt(),
+        })
+    }
+}
+
+/// a range of values and how many samples fell in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+
+/// splits the range of the samples into equal width bins and counts the
+/// samples in each. the last bin includes the max. there is only one bin if
+/// every sample is the same
+pub fn histogram(samples: &[f64], bins: usize) -> Vec<Bin> {
+    if samples.is_empty() || bins == 0 {
+        return Vec::new();
+    }
+
+    let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    if min == max {
+        return vec![Bin { lower: min, upper: max, count: samples.len() }];
+    }
+
+    let width = (max - min) / bins as f64;
+
+    let mut rtn: Vec<Bin> = (0..bins)
+        .map(|index| Bin {
+            lower: min + width * index as f64,
+            upper: if index + 1 == bins { max } else { min + width * (index + 1) as f64 },
+            count: 0,
+        })
+        .collect();
+
+    for sample in samples {
+        let index = (((sample - min) / width) as usize).min(bins - 1);
+
+        rtn[index].count += 1;
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 3.0);
+        assert_eq!(percentile(&sorted, 100.0), 5.0);
+        assert_eq!(percentile(&sorted, 12.5), 1.5);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn histogram_counts_every_sample() {
+        let samples = [0.0, 1.0, 2.5, 5.0, 9.9, 10.0];
+        let bins = histogram(&samples, 4);
+
+        assert_eq!(bins.len(), 4);
+        assert_eq!(bins.iter().map(|bin| bin.count).collect::<Vec<_>>(), vec![2, 1, 1, 2]);
+        assert_eq!(bins[0].lower, 0.0);
+        assert_eq!(bins[3].upper, 10.0);
+
+        let same = histogram(&[3.0, 3.0], 5);
+        assert_eq!(same, vec![Bin { lower: 3.0, upper: 3.0, count: 2 }]);
+    }
+}
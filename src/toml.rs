@@ -0,0 +1,158 @@
+use crate::error;
+
+/// a key value pair from a toml file along with the section it was in
+pub struct Entry {
+    pub section: String,
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// parses the value of a key. quoted strings support `\"` and `\\` escapes,
+/// anything else is taken as is
+fn parse_value(value: &str) -> Option<String> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return None;
+        }
+
+        return Some(value.to_owned());
+    };
+
+    let mut rtn = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => rtn.push(chars.next()?),
+            '"' => {
+                return if chars.as_str().trim().is_empty() {
+                    Some(rtn)
+                } else {
+                    None
+                };
+            },
+            _ => rtn.push(ch)
+        }
+    }
+
+    None
+}
+
+/// parses the small subset of toml used by the files in the inputs
+/// directory. only sections, comments and `key = value` pairs with strings or
+/// bare values are supported. every key must be inside of a section
+pub fn parse(contents: &str) -> error::Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut section: Option<&str> = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            section = Some(name.trim());
+            continue;
+        }
+
+        // the span of the trimmed line
+        let start = line.len() - line.trim_start().len();
+        let whole = start..(start + trimmed.len());
+
+        let Some(current) = section else {
+            return Err(error::build::bad_input(
+                error::Diagnostic::new(index + 1, line, whole, "expected a [section] before the first key")
+            ));
+        };
+
+        let Some(equals) = line.find('=') else {
+            return Err(error::build::bad_input(
+                error::Diagnostic::new(index + 1, line, whole, "expected a [section] or key = value")
+            ));
+        };
+
+        let key = line[..equals].trim();
+
+        if entries.iter().any(|entry| entry.section == current && entry.key == key) {
+            return Err(error::build::bad_input(
+                error::Diagnostic::new(index + 1, line, start..(start + key.len()), format!("expected \"{}\" to only be given once in [{}]", key, current))
+            ));
+        }
+
+        let raw = &line[(equals + 1)..];
+        let value_start = equals + 1 + raw.len() - raw.trim_start().len();
+        let span = value_start..(value_start + raw.trim().len());
+
+        let Some(value) = parse_value(raw.trim()) else {
+            return Err(error::build::bad_input(
+                error::Diagnostic::new(index + 1, line, span, "expected a quoted string or a value without spaces")
+            ));
+        };
+
+        entries.push(Entry {
+            section: current.to_owned(),
+            key: key.to_owned(),
+            value,
+            line: index + 1,
+        });
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// the line, span and reason of the error from parsing the contents
+    fn error_at(contents: &str) -> (usize, std::ops::Range<usize>, String) {
+        let err = parse(contents).err().unwrap();
+
+        assert!(matches!(err.kind, error::ErrorKind::BadInput));
+
+        let diagnostic = err.diagnostic.unwrap();
+
+        (diagnostic.line_number, diagnostic.span, diagnostic.reason)
+    }
+
+    #[test]
+    fn sections() {
+        let entries = parse("# comment\n[day1.real]\npart1 = 1\n\n  [ day2 ]  \n  part1 = 2 \n").unwrap();
+        let found: Vec<(&str, &str, &str, usize)> = entries.iter()
+            .map(|entry| (entry.section.as_str(), entry.key.as_str(), entry.value.as_str(), entry.line))
+            .collect();
+
+        assert_eq!(found, vec![("day1.real", "part1", "1", 3), ("day2", "part1", "2", 6)]);
+    }
+
+    #[test]
+    fn values() {
+        let entries = parse("[a]\nint = 10471\nneg = -3\nquoted = \"a b\"\nescaped = \"say \\\"hi\\\" \\\\ \"\nempty = \"\"\n").unwrap();
+        let values: Vec<&str> = entries.iter().map(|entry| entry.value.as_str()).collect();
+
+        assert_eq!(values, vec!["10471", "-3", "a b", "say \"hi\" \\ ", ""]);
+        assert_eq!(values[0].parse::<u64>(), Ok(10471));
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        assert_eq!(error_at("[a]\nkey = 1\n  key = 2\n"), (3, 2..5, String::from("expected \"key\" to only be given once in [a]")));
+
+        // the same key in another section is fine
+        assert_eq!(parse("[a]\nkey = 1\n[b]\nkey = 2\n").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(error_at("key = 1\n").0, 1);
+        assert_eq!(error_at("key = 1\n").1, 0..7);
+        assert_eq!(error_at("[a]\n  no equals\n").1, 2..11);
+        assert_eq!(error_at("[a]\nkey = a b\n").1, 6..9);
+        assert_eq!(error_at("[a]\nkey = \"open\n").1, 6..11);
+        assert_eq!(error_at("[a]\nkey = \"a\" b\n").1, 6..11);
+        assert_eq!(error_at("[a]\nkey =\n"), (2, 5..5, String::from("expected a quoted string or a value without spaces")));
+    }
+}
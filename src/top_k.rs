@@ -0,0 +1,178 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// an item in the heap. ordered by key and then by when it was pushed so
+/// that an earlier item ranks higher than a later one with the same key
+struct Slot<K, T> {
+    key: K,
+    order: u64,
+    item: T,
+}
+
+impl<K: Ord, T> Slot<K, T> {
+    fn rank(&self) -> (&K, Reverse<u64>) {
+        (&self.key, Reverse(self.order))
+    }
+}
+
+impl<K: Ord, T> PartialEq for Slot<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl<K: Ord, T> Eq for Slot<K, T> {}
+
+impl<K: Ord, T> PartialOrd for Slot<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> Ord for Slot<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// keeps the k items with the largest keys out of everything pushed
+///
+/// the lowest ranked item is kept at the top of a min heap so each push is
+/// O(log k) and only k items are ever stored. when keys are equal the item
+/// that was pushed first ranks higher so the results are always the same
+/// for the same input.
+pub struct TopK<K, T> {
+    k: usize,
+    pushed: u64,
+    heap: BinaryHeap<Reverse<Slot<K, T>>>,
+}
+
+impl<K: Ord, T> TopK<K, T> {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            pushed: 0,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// the most items that will be kept
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// the number of items currently kept
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// offers an item. it is kept if it ranks higher than the lowest kept
+    /// item or there is still room
+    pub fn push(&mut self, key: K, item: T) {
+        let slot = Slot { key, order: self.pushed, item };
+
+        self.pushed += 1;
+
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(slot));
+        } else if let Some(mut lowest) = self.heap.peek_mut() {
+            // a later item with an equal key ranks lower so it never
+            // replaces an earlier one
+            if slot > lowest.0 {
+                *lowest = Reverse(slot);
+            }
+        }
+    }
+
+    /// the key of the lowest ranked item that is kept
+    pub fn min_key(&self) -> Option<&K> {
+        self.heap.peek().map(|lowest| &lowest.0.key)
+    }
+
+    /// the kept items ordered from the highest ranked to the lowest
+    pub fn into_sorted_vec(self) -> Vec<(K, T)> {
+        // the reversed slots sort from lowest to highest ranked when
+        // ascending which is the highest to lowest for the slots themselves
+        self.heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(slot)| (slot.key, slot.item))
+            .collect()
+    }
+}
+
+impl<K: Ord, T> Extend<(K, T)> for TopK<K, T> {
+    fn extend<I: IntoIterator<Item = (K, T)>>(&mut self, iter: I) {
+        for (key, item) in iter {
+            self.push(key, item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// the expected result using a stable sort of everything
+    fn sorted(keys: &[u8], k: usize) -> Vec<(u8, usize)> {
+        let mut all: Vec<(u8, usize)> = keys.iter()
+            .copied()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+
+        all.sort_by_key(|(key, _)| Reverse(*key));
+        all.truncate(k);
+        all
+    }
+
+    #[test]
+    fn ties_keep_push_order() {
+        let mut top = TopK::new(3);
+        top.extend([(4, 'a'), (9, 'b'), (1, 'c'), (7, 'd'), (9, 'e'), (7, 'f')]);
+
+        assert_eq!(top.min_key(), Some(&7));
+        assert_eq!(top.into_sorted_vec(), vec![(9, 'b'), (9, 'e'), (7, 'd')]);
+    }
+
+    #[test]
+    fn zero_keeps_nothing() {
+        let mut top = TopK::new(0);
+        top.push(1, ());
+
+        assert!(top.is_empty());
+    }
+
+    #[test]
+    fn matches_stable_sort() {
+        // every sequence of up to 6 keys from 0 to 2
+        let mut sequences: Vec<Vec<u8>> = vec![Vec::new()];
+
+        for _ in 0..6 {
+            let mut next = Vec::with_capacity(sequences.len() * 3);
+
+            for sequence in &sequences {
+                for key in 0..3 {
+                    let mut added = sequence.clone();
+                    added.push(key);
+                    next.push(added);
+                }
+            }
+
+            for keys in &next {
+                for k in 0..=(keys.len() + 1) {
+                    let mut top = TopK::new(k);
+                    top.extend(keys.iter().copied().enumerate().map(|(index, key)| (key, index)));
+
+                    assert_eq!(top.len(), k.min(keys.len()));
+                    assert_eq!(top.into_sorted_vec(), sorted(keys, k), "{:?} k {}", keys, k);
+                }
+            }
+
+            sequences = next;
+        }
+    }
+}
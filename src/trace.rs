@@ -0,0 +1,144 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::error;
+use crate::cli;
+
+/// how detailed the output is. each level includes the output of the levels
+/// below it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// the notable steps of a day, `-v`
+    Debug = 1,
+    /// every step of a day, `-vv`
+    Trace = 2,
+}
+
+impl Level {
+    /// the level for the number of times `-v` was given
+    pub fn from_verbosity(count: usize) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Debug => f.write_str("DEBUG"),
+            Level::Trace => f.write_str("TRACE"),
+        }
+    }
+}
+
+/// the options that control the trace output
+pub const OPTIONS: &[cli::Opt] = &[
+    cli::Opt::flag("verbose", "shows debug output. given twice shows trace output")
+        .short('v'),
+    cli::Opt::new("trace", cli::ValueKind::Usize, "only show the output of the given day. implies -vv if -v is not given")
+        .multiple(),
+    cli::Opt::new("trace-file", cli::ValueKind::Path, "writes the output to a file instead of stderr"),
+];
+
+struct State {
+    /// the targets to show. empty shows every target
+    targets: Vec<String>,
+    /// where the output is written. stderr when not set
+    sink: Option<Box<dyn Write + Send>>,
+}
+
+/// the current level, 0 when disabled. kept outside of the state so that
+/// checking if tracing is enabled does not need the lock
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+static STATE: Mutex<State> = Mutex::new(State {
+    targets: Vec::new(),
+    sink: None,
+});
+
+/// sets the level, targets and output of tracing. a level of `None` disables
+/// tracing
+pub fn init(level: Option<Level>, targets: Vec<String>, sink: Option<Box<dyn Write + Send>>) {
+    let mut state = STATE.lock().unwrap_or_else(|err| err.into_inner());
+    state.targets = targets;
+    state.sink = sink;
+
+    LEVEL.store(level.map(|v| v as u8).unwrap_or(0), Ordering::Relaxed);
+}
+
+/// sets up tracing from the values of [`OPTIONS`]
+pub fn configure(matches: &cli::Matches) -> error::Result<()> {
+    let mut targets = Vec::new();
+
+    for value in matches.values("trace") {
+        let Ok(day) = value.parse::<u8>() else {
+            return Err(error::build::invalid_value("trace", "<usize>", value));
+        };
+
+        targets.push(format!("day{}", day));
+    }
+
+    let mut level = Level::from_verbosity(matches.count("verbose"));
+
+    if level.is_none() && !targets.is_empty() {
+        level = Some(Level::Trace);
+    }
+
+    let sink: Option<Box<dyn Write + Send>> = match matches.get::<PathBuf>("trace-file")? {
+        Some(path) => match std::fs::File::create(&path) {
+            Ok(file) => Some(Box::new(std::io::LineWriter::new(file))),
+            Err(err) => {
+                return Err(error::Error::new(error::ErrorKind::IoError)
+                    .with_message(format!("failed to create trace file. {}", path.display()))
+                    .with_error(err));
+            }
+        },
+        None => None
+    };
+
+    init(level, targets, sink);
+
+    Ok(())
+}
+
+/// checks if output for the level and target would be written
+pub fn enabled(level: Level, target: &str) -> bool {
+    if (level as u8) > LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    let state = STATE.lock().unwrap_or_else(|err| err.into_inner());
+
+    state.targets.is_empty() || state.targets.iter().any(|v| v == target)
+}
+
+/// writes a line of output. use the [`trace!`](crate::trace!) macro instead
+/// so the message is only formatted when the output is enabled
+pub fn write(level: Level, target: &str, args: std::fmt::Arguments<'_>) {
+    let mut state = STATE.lock().unwrap_or_else(|err| err.into_inner());
+
+    // failing to write trace output is not worth stopping the day for
+    let _ = match state.sink.as_mut() {
+        Some(sink) => writeln!(sink, "{} {}: {}", level, target, args),
+        None => writeln!(std::io::stderr().lock(), "{} {}: {}", level, target, args),
+    };
+}
+
+/// writes a line of output for a target if the level is enabled
+///
+/// ```ignore
+/// crate::trace!(Trace, "day6", "packet seq: {:?}", seq);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $target:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::$level, $target) {
+            $crate::trace::write($crate::trace::Level::$level, $target, format_args!($($arg)+));
+        }
+    };
+}